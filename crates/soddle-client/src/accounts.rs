@@ -0,0 +1,95 @@
+use anchor_lang::AccountDeserialize;
+
+pub use soddle::states::competition::Competition;
+pub use soddle::states::fee_ledger::FeeLedger;
+pub use soddle::states::fee_vault::FeeVault;
+pub use soddle::states::game_config::GameConfig;
+pub use soddle::states::game_metrics::GameMetrics;
+pub use soddle::states::game_session::GameSession;
+pub use soddle::states::kol::Kol;
+pub use soddle::states::player_profile::PlayerProfile;
+pub use soddle::states::referral::Referral;
+pub use soddle::states::season_pass::SeasonPass;
+pub use soddle::states::token_metrics::TokenMetrics;
+
+/// Decodes raw account data (discriminator included) into one of the program's accounts.
+/// The discriminator is checked, so passing a `Kol` buffer as a `GameSession` fails.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> anchor_lang::Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data)
+}
+
+#[cfg(feature = "rpc")]
+pub mod rpc {
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::AccountDeserialize;
+    use solana_client::rpc_client::RpcClient;
+
+    use super::*;
+    use crate::{pda, ClientError};
+
+    pub fn fetch<T: AccountDeserialize>(client: &RpcClient, address: &Pubkey) -> Result<T, ClientError> {
+        let account = client.get_account(address)?;
+        if account.owner != soddle::ID {
+            return Err(ClientError::WrongOwner(*address));
+        }
+        Ok(decode(&account.data)?)
+    }
+
+    pub fn fetch_game_session(
+        client: &RpcClient,
+        player: &Pubkey,
+        competition_id: u64,
+        start_time: i64,
+    ) -> Result<GameSession, ClientError> {
+        fetch(client, &pda::game_session(player, competition_id, start_time).0)
+    }
+
+    pub fn fetch_kol(client: &RpcClient, id: u64) -> Result<Kol, ClientError> {
+        fetch(client, &pda::kol(id).0)
+    }
+
+    pub fn fetch_competition(
+        client: &RpcClient,
+        game_state: &Pubkey,
+        id: u64,
+    ) -> Result<Competition, ClientError> {
+        fetch(client, &pda::competition(game_state, id).0)
+    }
+
+    pub fn fetch_game_metrics(client: &RpcClient) -> Result<GameMetrics, ClientError> {
+        fetch(client, &pda::game_metrics().0)
+    }
+
+    pub fn fetch_fee_vault(client: &RpcClient) -> Result<FeeVault, ClientError> {
+        fetch(client, &pda::fee_vault().0)
+    }
+
+    pub fn fetch_game_config(client: &RpcClient) -> Result<GameConfig, ClientError> {
+        fetch(client, &pda::game_config().0)
+    }
+
+    pub fn fetch_player_profile(client: &RpcClient, player: &Pubkey) -> Result<PlayerProfile, ClientError> {
+        fetch(client, &pda::player_profile(player).0)
+    }
+
+    pub fn fetch_treasury_ledger(client: &RpcClient, mint: Option<Pubkey>) -> Result<FeeLedger, ClientError> {
+        fetch(client, &pda::treasury_ledger(mint).0)
+    }
+
+    pub fn fetch_kol_ledger(client: &RpcClient, kol_id: u64, mint: Option<Pubkey>) -> Result<FeeLedger, ClientError> {
+        fetch(client, &pda::kol_ledger(&pda::kol(kol_id).0, mint).0)
+    }
+
+    pub fn fetch_referral(client: &RpcClient, player: &Pubkey) -> Result<Referral, ClientError> {
+        fetch(client, &pda::referral(player).0)
+    }
+
+    pub fn fetch_season_pass(client: &RpcClient, player: &Pubkey) -> Result<SeasonPass, ClientError> {
+        fetch(client, &pda::season_pass(player).0)
+    }
+
+    pub fn fetch_token_metrics(client: &RpcClient, mint: &Pubkey) -> Result<TokenMetrics, ClientError> {
+        fetch(client, &pda::token_metrics(mint).0)
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::solana_program::instruction::InstructionError;
+
+pub use soddle::errors::SoddleError;
+
+// Declaration order of `SoddleError`; anchor assigns codes from `ERROR_CODE_OFFSET` upwards.
+const SODDLE_ERRORS: &[SoddleError] = &[
+    SoddleError::GameSessionNotEnded,
+    SoddleError::InvalidCompetition,
+    SoddleError::InvalidTimeRange,
+    SoddleError::CompetitionStarted,
+    SoddleError::MaxGuessesReachedForGame1,
+    SoddleError::MaxGuessesReachedForGame2,
+    SoddleError::MaxGuessesReachedForGame3,
+    SoddleError::InvalidKOLCount,
+    SoddleError::InvalidGameType,
+    SoddleError::GameAlreadyPlayed,
+    SoddleError::GameAlreadyCompleted,
+    SoddleError::InvalidGuessIndex,
+    SoddleError::CompetitionNotEnded,
+    SoddleError::GameNotCompleted,
+    SoddleError::InvalidPlayer,
+    SoddleError::UnauthorizedAuthority,
+    SoddleError::AlreadyPlayedToday,
+    SoddleError::MathOverflow,
+    SoddleError::EmptyVault,
+    SoddleError::GameSessionAlreadyStarted,
+    SoddleError::NumericOverflow,
+    SoddleError::InvalidWithdrawAmount,
+    SoddleError::InsufficientFunds,
+    SoddleError::SessionExpired,
+    SoddleError::InvalidKol,
+    SoddleError::GameNotStarted,
+    SoddleError::AttemptAlreadyCompleted,
+    SoddleError::InvalidGuessCount,
+    SoddleError::MaxAttemptsReached,
+    SoddleError::AttemptTimedOut,
+    SoddleError::TooManyGuesses,
+    SoddleError::InvalidOracle,
+    SoddleError::InvalidOracleSignature,
+    SoddleError::InvalidStartTime,
+    SoddleError::InsufficientFundsForRent,
+    SoddleError::InvalidFeeVault,
+    SoddleError::InvalidScoreModel,
+    SoddleError::InvalidScoringRule,
+    SoddleError::InvalidStreakConfig,
+    SoddleError::StreakFreezeLimitReached,
+    SoddleError::BadgeNotEarned,
+    SoddleError::BadgeAlreadyClaimed,
+    SoddleError::InvalidFeeMint,
+    SoddleError::MissingTokenAccounts,
+    SoddleError::InvalidFeeAmount,
+    SoddleError::InvalidFeeSplit,
+    SoddleError::InvalidPayoutWallet,
+    SoddleError::NoRewardsToClaim,
+    SoddleError::SelfReferral,
+    SoddleError::ReferrerAlreadySet,
+    SoddleError::ReferralCycle,
+    SoddleError::MissingReferrerLedger,
+    SoddleError::InvalidVoucher,
+    SoddleError::VoucherExpired,
+    SoddleError::VoucherAlreadyUsed,
+    SoddleError::MissingVoucherAccounts,
+    SoddleError::SeasonPassUnavailable,
+    SoddleError::InvalidSeasonPassConfig,
+    SoddleError::BonusAttemptsUnavailable,
+    SoddleError::BonusAttemptLimitReached,
+    SoddleError::PracticeSession,
+    SoddleError::NotPracticeSession,
+    SoddleError::TokenGateNotMet,
+    SoddleError::InvalidTokenGate,
+    SoddleError::NotOnAllowlist,
+    SoddleError::InvalidReferralChain,
+    SoddleError::MissingFeeLedgers,
+    SoddleError::UnsupportedMintExtension,
+    SoddleError::SessionNotExpired,
+];
+
+/// Maps a custom program error code (as found in `InstructionError::Custom`) back to `SoddleError`.
+pub fn decode_error_code(code: u32) -> Option<SoddleError> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)? as usize;
+    SODDLE_ERRORS.get(index).copied()
+}
+
+pub fn decode_instruction_error(error: &InstructionError) -> Option<SoddleError> {
+    match error {
+        InstructionError::Custom(code) => decode_error_code(*code),
+        _ => None,
+    }
+}
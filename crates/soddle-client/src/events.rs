@@ -0,0 +1,135 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+pub use soddle::constants::EVENT_SCHEMA_VERSION;
+pub use soddle::events::*;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+pub enum SoddleEvent {
+    GameSessionStarted(GameSessionStarted),
+    GuessAttempted(GuessAttempted),
+    GameSessionCompleted(GameSessionCompleted),
+    AttemptCompleted(AttemptCompleted),
+    GameSessionEnded(GameSessionEnded),
+    StreakUpdated(StreakUpdated),
+    AchievementUnlocked(AchievementUnlocked),
+    NewHighScore(NewHighScore),
+}
+
+/// A soddle event written under a schema version other than `EVENT_SCHEMA_VERSION`. Its layout
+/// may differ from the current one, so it is reported instead of being decoded or skipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedVersion {
+    pub event: &'static str,
+    pub version: u8,
+}
+
+impl std::fmt::Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} event of schema version {}, only version {EVENT_SCHEMA_VERSION} is supported",
+            self.event, self.version
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedVersion {}
+
+fn decode_as<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    let body = data.strip_prefix(&T::DISCRIMINATOR[..])?;
+    T::deserialize(&mut &body[..]).ok()
+}
+
+type Decoder = fn(&[u8]) -> Option<SoddleEvent>;
+
+const DECODERS: [(&[u8], &str, Decoder); 8] = [
+    (&GameSessionStarted::DISCRIMINATOR, "GameSessionStarted", |data| {
+        decode_as(data).map(SoddleEvent::GameSessionStarted)
+    }),
+    (&GuessAttempted::DISCRIMINATOR, "GuessAttempted", |data| decode_as(data).map(SoddleEvent::GuessAttempted)),
+    (&GameSessionCompleted::DISCRIMINATOR, "GameSessionCompleted", |data| {
+        decode_as(data).map(SoddleEvent::GameSessionCompleted)
+    }),
+    (&AttemptCompleted::DISCRIMINATOR, "AttemptCompleted", |data| decode_as(data).map(SoddleEvent::AttemptCompleted)),
+    (&GameSessionEnded::DISCRIMINATOR, "GameSessionEnded", |data| decode_as(data).map(SoddleEvent::GameSessionEnded)),
+    (&StreakUpdated::DISCRIMINATOR, "StreakUpdated", |data| decode_as(data).map(SoddleEvent::StreakUpdated)),
+    (&AchievementUnlocked::DISCRIMINATOR, "AchievementUnlocked", |data| {
+        decode_as(data).map(SoddleEvent::AchievementUnlocked)
+    }),
+    (&NewHighScore::DISCRIMINATOR, "NewHighScore", |data| decode_as(data).map(SoddleEvent::NewHighScore)),
+];
+
+impl SoddleEvent {
+    /// Decodes a serialized event (8 byte discriminator followed by the borsh body).
+    /// `Ok(None)` is data that is not a soddle event.
+    pub fn decode(data: &[u8]) -> Result<Option<Self>, UnsupportedVersion> {
+        let Some((_, event, decoder)) = DECODERS.iter().find(|(discriminator, ..)| data.starts_with(discriminator)) else {
+            return Ok(None);
+        };
+        // The schema version is the first field of every event; layouts from other versions
+        // would deserialize into garbage rather than fail.
+        match data.get(8) {
+            Some(&version) if version != EVENT_SCHEMA_VERSION => Err(UnsupportedVersion { event, version }),
+            _ => Ok(decoder(data)),
+        }
+    }
+
+    /// Decodes the data of the self-CPI instruction that `emit_cpi!` issues, i.e. the event
+    /// prefixed with anchor's event instruction tag.
+    pub fn decode_cpi(ix_data: &[u8]) -> Result<Option<Self>, UnsupportedVersion> {
+        match ix_data.strip_prefix(&EVENT_IX_TAG_LE[..]) {
+            Some(data) => Self::decode(data),
+            None => Ok(None),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SoddleEvent::GameSessionStarted(_) => "GameSessionStarted",
+            SoddleEvent::GuessAttempted(_) => "GuessAttempted",
+            SoddleEvent::GameSessionCompleted(_) => "GameSessionCompleted",
+            SoddleEvent::AttemptCompleted(_) => "AttemptCompleted",
+            SoddleEvent::GameSessionEnded(_) => "GameSessionEnded",
+            SoddleEvent::StreakUpdated(_) => "StreakUpdated",
+            SoddleEvent::AchievementUnlocked(_) => "AchievementUnlocked",
+            SoddleEvent::NewHighScore(_) => "NewHighScore",
+        }
+    }
+}
+
+/// Extracts events emitted with `emit!` from a transaction's log messages. Events emitted with
+/// `emit_cpi!` never reach the logs; decode those from inner instructions with `decode_cpi`.
+///
+/// `Program data:` lines are only attributed to soddle while it is the innermost program on
+/// the invocation stack, so data logged by programs it CPIs into (or that CPI into it) is skipped.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Result<Vec<SoddleEvent>, UnsupportedVersion> {
+    let program_id = soddle::ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            if stack.last() == Some(&program_id.as_str()) {
+                if let Ok(raw) = STANDARD.decode(data) {
+                    events.extend(SoddleEvent::decode(&raw)?);
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(id), Some("invoke")) => stack.push(id),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(events)
+}
@@ -0,0 +1,674 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::system_program;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use anchor_spl::token_2022;
+use soddle::achievements::Achievement;
+use soddle::states::game_session::GameSession;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle::states::token_gate::TokenGate;
+use soddle::states::voucher::SignedVoucher;
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: soddle::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Arguments of `initialize_kol`, grouped so callers don't juggle nine positionals.
+#[derive(Clone, Debug, Default)]
+pub struct KolInfo {
+    pub id: u64,
+    pub name: String,
+    pub age: u8,
+    pub country: String,
+    pub pfp_type: String,
+    pub pfp: String,
+    pub account_creation: u16,
+    pub followers: u32,
+    pub ecosystem: String,
+}
+
+/// The token side of a fee, refund or withdrawal in an SPL token: the mint, the
+/// program that owns it (SPL Token or Token-2022) and the user's token account.
+#[derive(Clone, Copy, Debug)]
+pub struct TokenFee {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub token_account: Pubkey,
+}
+
+/// A token account of the player, shown to enter a token-gated competition.
+#[derive(Clone, Copy, Debug)]
+pub struct TokenHolding {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+}
+
+/// The optional parts of `start_game_session`, all off by default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AttemptOptions {
+    /// The player's registered referrer.
+    pub referrer: Option<Pubkey>,
+    /// Required when the competition charges in an SPL token.
+    pub fee: Option<TokenFee>,
+    /// Has to be preceded by `ed25519_verify` of its message by the metrics authority.
+    pub voucher: Option<SignedVoucher>,
+    /// Whether the player holds a season pass.
+    pub season_pass: bool,
+    /// Required when the competition is token-gated.
+    pub holding: Option<TokenHolding>,
+    /// Set for practice sessions, which pay no fee and so pass no fee ledgers.
+    pub practice: bool,
+}
+
+pub fn initialize_game_state(payer: &Pubkey) -> Instruction {
+    build(
+        soddle::accounts::InitializeGameState {
+            game_state: pda::game_state(payer).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::InitializeGameState {},
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_competition(
+    authority: &Pubkey,
+    game_state: &Pubkey,
+    id: u64,
+    start_time: i64,
+    end_time: i64,
+    scoring_rule: ScoringRule,
+    score_model: ScoreModel,
+    fee_mint: Option<Pubkey>,
+    fee_amount: u64,
+    token_gate: Option<TokenGate>,
+) -> Instruction {
+    build(
+        soddle::accounts::InitializeCompetition {
+            game_state: *game_state,
+            competition: pda::competition(game_state, id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::InitializeCompetition {
+            id,
+            start_time,
+            end_time,
+            scoring_rule,
+            score_model,
+            fee_mint,
+            fee_amount,
+            token_gate,
+        },
+    )
+}
+
+pub fn initialize_metrics(authority: &Pubkey) -> Instruction {
+    build(
+        soddle::accounts::InitializeGameMetrics {
+            metrics: pda::game_metrics().0,
+            fee_vault: pda::fee_vault().0,
+            config: pda::game_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::InitializeMetrics {},
+    )
+}
+
+pub fn initialize_token_vault(authority: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Instruction {
+    build(
+        soddle::accounts::InitializeTokenVault {
+            metrics: pda::game_metrics().0,
+            fee_vault: pda::fee_vault().0,
+            fee_mint: *mint,
+            token_vault: pda::token_vault(mint).0,
+            token_metrics: pda::token_metrics(mint).0,
+            authority: *authority,
+            token_program: *token_program,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::InitializeTokenVault {},
+    )
+}
+
+pub fn initialize_kol(payer: &Pubkey, info: KolInfo) -> Instruction {
+    build(
+        soddle::accounts::InitializeKol {
+            kol: pda::kol(info.id).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::InitializeKol {
+            id: info.id,
+            name: info.name,
+            age: info.age,
+            country: info.country,
+            pfp_type: info.pfp_type,
+            pfp: info.pfp,
+            account_creation: info.account_creation,
+            followers: info.followers,
+            ecosystem: info.ecosystem,
+        },
+    )
+}
+
+/// `proof` is only checked for invite-only competitions; build it with `allowlist::proof`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_game_session(
+    player: &Pubkey,
+    kol: &Pubkey,
+    competition: &Pubkey,
+    competition_id: u64,
+    start_time: i64,
+    game_type: u8,
+    is_practice: bool,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        soddle::accounts::InitializeGameSession {
+            game_session: pda::game_session(player, competition_id, start_time).0,
+            kol: *kol,
+            competition: *competition,
+            player: *player,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::InitializeGameSession {
+            competition_id,
+            start_time,
+            game_type,
+            is_practice,
+            proof,
+        },
+    )
+}
+
+/// Practice sessions take their answers from a separate set, under their own verifier.
+pub fn initialize_verifier(
+    oracle_authority: &Pubkey,
+    game_session: &Pubkey,
+    is_practice: bool,
+    answer_hash: [u8; 32],
+) -> Instruction {
+    build(
+        soddle::accounts::InitializeVerifier {
+            verifier: pda::verifier(game_session, is_practice).0,
+            game_session: *game_session,
+            oracle_authority: *oracle_authority,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::InitializeVerifier { answer_hash },
+    )
+}
+
+pub fn start_game_session(
+    player: &Pubkey,
+    kol: &Pubkey,
+    competition: &Pubkey,
+    competition_id: u64,
+    start_time: i64,
+    options: AttemptOptions,
+) -> Instruction {
+    let AttemptOptions { referrer, fee, voucher, season_pass, holding, practice } = options;
+    build(
+        soddle::accounts::StartGameSession {
+            game_session: pda::game_session(player, competition_id, start_time).0,
+            kol: *kol,
+            competition: *competition,
+            metrics: pda::game_metrics().0,
+            fee_vault: pda::fee_vault().0,
+            config: pda::game_config().0,
+            treasury_ledger: (!practice).then(|| pda::treasury_ledger(fee.map(|fee| fee.mint)).0),
+            prize_pool_ledger: (!practice).then(|| pda::prize_pool_ledger(competition, fee.map(|fee| fee.mint)).0),
+            kol_ledger: (!practice).then(|| pda::kol_ledger(kol, fee.map(|fee| fee.mint)).0),
+            referral: pda::referral(player).0,
+            referrer_ledger: referrer.filter(|_| !practice).map(|referrer| pda::referrer_ledger(&referrer, fee.map(|fee| fee.mint)).0),
+            season_pass: season_pass.then(|| pda::season_pass(player).0),
+            player: *player,
+            system_program: system_program::ID,
+            fee_mint: fee.map(|fee| fee.mint),
+            token_vault: fee.map(|fee| pda::token_vault(&fee.mint).0),
+            token_metrics: fee.map(|fee| pda::token_metrics(&fee.mint).0),
+            player_token_account: fee.map(|fee| fee.token_account),
+            token_program: fee.map(|fee| fee.token_program),
+            gate_token_account: holding.map(|holding| holding.token_account),
+            gate_mint: holding.map(|holding| holding.mint),
+            instructions: voucher.map(|_| sysvar_instructions::ID),
+            voucher_bitmap: voucher.map(|signed| pda::voucher_bitmap(signed.voucher.bitmap_page()).0),
+            event_authority: pda::event_authority().0,
+            program: soddle::ID,
+        },
+        soddle::instruction::StartGameSession {
+            competition_id,
+            start_time,
+            voucher,
+        },
+    )
+}
+
+pub fn make_guess(
+    player: &Pubkey,
+    oracle_authority: &Pubkey,
+    game_session: &Pubkey,
+    is_practice: bool,
+    competition: &Pubkey,
+    guess: String,
+    oracle_signature: [u8; 64],
+) -> Instruction {
+    build(
+        soddle::accounts::MakeGuess {
+            game_session: *game_session,
+            verifier: pda::verifier(game_session, is_practice).0,
+            competition: *competition,
+            oracle_authority: *oracle_authority,
+            player: *player,
+            instructions: sysvar_instructions::ID,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority().0,
+            program: soddle::ID,
+        },
+        soddle::instruction::MakeGuess {
+            guess,
+            oracle_signature,
+        },
+    )
+}
+
+/// The message the oracle signs for a guess: `hash(guess) || answer_hash`.
+pub fn guess_message(guess: &str, answer_hash: &[u8; 32]) -> Vec<u8> {
+    [&hash(guess.as_bytes()).to_bytes()[..], &answer_hash[..]].concat()
+}
+
+/// An Ed25519 precompile instruction verifying `signature` over `message`. It must be placed
+/// immediately before the instruction that checks it (e.g. `make_guess`).
+pub fn ed25519_verify(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    const HEADER_LEN: u16 = 16;
+    let public_key_offset = HEADER_LEN;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1u8, 0];
+    for field in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+pub fn close_practice_session(player: &Pubkey, game_session: &Pubkey) -> Instruction {
+    build(
+        soddle::accounts::ClosePracticeSession {
+            game_session: *game_session,
+            player: *player,
+        },
+        soddle::instruction::ClosePracticeSession {},
+    )
+}
+
+/// `referrer` and `fee` must be given when the session was charged with a referrer or
+/// its deposit was paid in an SPL token.
+pub fn end_game_session(
+    player: &Pubkey,
+    game_session: &Pubkey,
+    competition: &Pubkey,
+    kol: &Pubkey,
+    referrer: Option<Pubkey>,
+    fee: Option<TokenFee>,
+) -> Instruction {
+    build(
+        soddle::accounts::EndGameSession {
+            game_session: *game_session,
+            metrics: pda::game_metrics().0,
+            fee_vault: pda::fee_vault().0,
+            config: pda::game_config().0,
+            competition: *competition,
+            treasury_ledger: pda::treasury_ledger(fee.map(|fee| fee.mint)).0,
+            prize_pool_ledger: pda::prize_pool_ledger(competition, fee.map(|fee| fee.mint)).0,
+            kol_ledger: pda::kol_ledger(kol, fee.map(|fee| fee.mint)).0,
+            referrer_ledger: referrer.map(|referrer| pda::referrer_ledger(&referrer, fee.map(|fee| fee.mint)).0),
+            player_profile: pda::player_profile(player).0,
+            player: *player,
+            system_program: system_program::ID,
+            fee_mint: fee.map(|fee| fee.mint),
+            token_vault: fee.map(|fee| pda::token_vault(&fee.mint).0),
+            token_metrics: fee.map(|fee| pda::token_metrics(&fee.mint).0),
+            player_token_account: fee.map(|fee| fee.token_account),
+            token_program: fee.map(|fee| fee.token_program),
+            event_authority: pda::event_authority().0,
+            program: soddle::ID,
+        },
+        soddle::instruction::EndGameSession {},
+    )
+}
+
+/// Settles an expired session that was never ended; anyone may send it. Only the ledgers
+/// `session` still owes a share are passed, as a session without any may predate them.
+pub fn expire_game_session(address: &Pubkey, session: &GameSession) -> Instruction {
+    let mint = session.fee_mint;
+    let split = session.fee_split;
+    build(
+        soddle::accounts::ExpireGameSession {
+            game_session: *address,
+            fee_vault: pda::fee_vault().0,
+            competition: session.competition,
+            treasury_ledger: (split.treasury > 0).then(|| pda::treasury_ledger(mint).0),
+            prize_pool_ledger: (split.prize_pool > 0).then(|| pda::prize_pool_ledger(&session.competition, mint).0),
+            kol_ledger: (split.kol > 0).then(|| pda::kol_ledger(&session.kol, mint).0),
+            referrer_ledger: session
+                .referrer
+                .filter(|_| split.referrer > 0)
+                .map(|referrer| pda::referrer_ledger(&referrer, mint).0),
+            player: session.player,
+        },
+        soddle::instruction::ExpireGameSession {},
+    )
+}
+
+/// `None` pauses claims; the KOL's share keeps accruing either way.
+pub fn set_kol_payout_wallet(authority: &Pubkey, kol_id: u64, payout_wallet: Option<Pubkey>) -> Instruction {
+    build(
+        soddle::accounts::SetKolPayoutWallet {
+            kol: pda::kol(kol_id).0,
+            metrics: pda::game_metrics().0,
+            authority: *authority,
+        },
+        soddle::instruction::SetKolPayoutWallet { payout_wallet },
+    )
+}
+
+/// Pays the KOL's whole lamport balance, or with `fee` set its balance in that mint, to
+/// the payout wallet (`fee.token_account` must belong to it).
+pub fn claim_kol_rewards(payout_wallet: &Pubkey, kol_id: u64, fee: Option<TokenFee>) -> Instruction {
+    let kol = pda::kol(kol_id).0;
+    build(
+        soddle::accounts::ClaimKolRewards {
+            kol,
+            kol_ledger: pda::kol_ledger(&kol, fee.map(|fee| fee.mint)).0,
+            fee_vault: pda::fee_vault().0,
+            payout_wallet: *payout_wallet,
+            fee_mint: fee.map(|fee| fee.mint),
+            token_vault: fee.map(|fee| pda::token_vault(&fee.mint).0),
+            payout_token_account: fee.map(|fee| fee.token_account),
+            token_program: fee.map(|fee| fee.token_program),
+        },
+        soddle::instruction::ClaimKolRewards {},
+    )
+}
+
+pub fn set_streak_config(
+    authority: &Pubkey,
+    streak_bonus_bps: u16,
+    max_streak_bonus_bps: u16,
+    streak_freeze_price: u64,
+    max_streak_freezes: u8,
+) -> Instruction {
+    build(
+        soddle::accounts::SetStreakConfig {
+            config: pda::game_config().0,
+            metrics: pda::game_metrics().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::SetStreakConfig {
+            streak_bonus_bps,
+            max_streak_bonus_bps,
+            streak_freeze_price,
+            max_streak_freezes,
+        },
+    )
+}
+
+/// `ancestors` are the referrer's own referrers, nearest first, up to the top of the
+/// chain; the program walks them to make sure the player isn't among them.
+pub fn register_referrer(player: &Pubkey, referrer: &Pubkey, ancestors: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        soddle::accounts::RegisterReferrer {
+            referral: pda::referral(player).0,
+            referrer_referral: pda::referral(referrer).0,
+            player: *player,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::RegisterReferrer { referrer: *referrer },
+    );
+    ix.accounts.extend(
+        ancestors.iter().map(|ancestor| AccountMeta::new_readonly(pda::referral(ancestor).0, false)),
+    );
+    ix
+}
+
+/// Pays the referrer's whole lamport balance, or with `fee` set its balance in that
+/// mint, to the referrer (`fee.token_account` must belong to them).
+pub fn claim_referral_rewards(referrer: &Pubkey, fee: Option<TokenFee>) -> Instruction {
+    build(
+        soddle::accounts::ClaimReferralRewards {
+            referrer_ledger: pda::referrer_ledger(referrer, fee.map(|fee| fee.mint)).0,
+            fee_vault: pda::fee_vault().0,
+            referrer: *referrer,
+            fee_mint: fee.map(|fee| fee.mint),
+            token_vault: fee.map(|fee| pda::token_vault(&fee.mint).0),
+            referrer_token_account: fee.map(|fee| fee.token_account),
+            token_program: fee.map(|fee| fee.token_program),
+        },
+        soddle::instruction::ClaimReferralRewards {},
+    )
+}
+
+/// Makes the competition invite-only (or open again, with None) until it starts.
+pub fn set_allowlist_root(
+    authority: &Pubkey,
+    competition: &Pubkey,
+    allowlist_root: Option<[u8; 32]>,
+) -> Instruction {
+    build(
+        soddle::accounts::SetAllowlistRoot {
+            game_state: pda::game_state(authority).0,
+            competition: *competition,
+            authority: *authority,
+        },
+        soddle::instruction::SetAllowlistRoot { allowlist_root },
+    )
+}
+
+/// Shares of the entry fee in basis points; the four have to add up to 10_000.
+pub fn set_fee_split(
+    authority: &Pubkey,
+    treasury_share_bps: u16,
+    prize_pool_share_bps: u16,
+    kol_share_bps: u16,
+    referrer_share_bps: u16,
+) -> Instruction {
+    build(
+        soddle::accounts::SetFeeSplit {
+            config: pda::game_config().0,
+            metrics: pda::game_metrics().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::SetFeeSplit {
+            treasury_share_bps,
+            prize_pool_share_bps,
+            kol_share_bps,
+            referrer_share_bps,
+        },
+    )
+}
+
+pub fn buy_streak_freeze(player: &Pubkey) -> Instruction {
+    build(
+        soddle::accounts::BuyStreakFreeze {
+            player_profile: pda::player_profile(player).0,
+            config: pda::game_config().0,
+            metrics: pda::game_metrics().0,
+            fee_vault: pda::fee_vault().0,
+            treasury_ledger: pda::treasury_ledger(None).0,
+            player: *player,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::BuyStreakFreeze {},
+    )
+}
+
+pub fn set_season_pass_config(
+    authority: &Pubkey,
+    season_pass_price: u64,
+    season_pass_duration: i64,
+    season_pass_bonus_attempts: u8,
+) -> Instruction {
+    build(
+        soddle::accounts::SetSeasonPassConfig {
+            config: pda::game_config().0,
+            metrics: pda::game_metrics().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::SetSeasonPassConfig {
+            season_pass_price,
+            season_pass_duration,
+            season_pass_bonus_attempts,
+        },
+    )
+}
+
+pub fn set_bonus_attempt_config(
+    authority: &Pubkey,
+    bonus_attempt_price: u64,
+    bonus_attempt_price_step_bps: u16,
+) -> Instruction {
+    build(
+        soddle::accounts::SetBonusAttemptConfig {
+            config: pda::game_config().0,
+            metrics: pda::game_metrics().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::SetBonusAttemptConfig {
+            bonus_attempt_price,
+            bonus_attempt_price_step_bps,
+        },
+    )
+}
+
+/// `season_pass` must be set when the player holds one, whose attempts come first.
+pub fn buy_bonus_attempt(player: &Pubkey, game_session: &Pubkey, season_pass: bool) -> Instruction {
+    build(
+        soddle::accounts::BuyBonusAttempt {
+            game_session: *game_session,
+            season_pass: season_pass.then(|| pda::season_pass(player).0),
+            player_profile: pda::player_profile(player).0,
+            config: pda::game_config().0,
+            metrics: pda::game_metrics().0,
+            fee_vault: pda::fee_vault().0,
+            treasury_ledger: pda::treasury_ledger(None).0,
+            player: *player,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::BuyBonusAttempt {},
+    )
+}
+
+pub fn buy_season_pass(player: &Pubkey) -> Instruction {
+    build(
+        soddle::accounts::BuySeasonPass {
+            season_pass: pda::season_pass(player).0,
+            config: pda::game_config().0,
+            metrics: pda::game_metrics().0,
+            fee_vault: pda::fee_vault().0,
+            treasury_ledger: pda::treasury_ledger(None).0,
+            player: *player,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::BuySeasonPass {},
+    )
+}
+
+pub fn create_badge_mint(
+    authority: &Pubkey,
+    achievement: Achievement,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    build(
+        soddle::accounts::CreateBadgeMint {
+            badge_mint: pda::badge_mint(achievement).0,
+            metrics: pda::game_metrics().0,
+            authority: *authority,
+            token_program: token_2022::ID,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::CreateBadgeMint {
+            achievement,
+            name,
+            symbol,
+            uri,
+        },
+    )
+}
+
+/// The player's Token-2022 associated token account for the badge of `achievement`.
+pub fn badge_account(player: &Pubkey, achievement: Achievement) -> Pubkey {
+    get_associated_token_address_with_program_id(player, &pda::badge_mint(achievement).0, &token_2022::ID)
+}
+
+pub fn claim_badge(player: &Pubkey, achievement: Achievement) -> Instruction {
+    build(
+        soddle::accounts::ClaimBadge {
+            player_profile: pda::player_profile(player).0,
+            badge_mint: pda::badge_mint(achievement).0,
+            badge_account: badge_account(player, achievement),
+            player: *player,
+            token_program: token_2022::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        soddle::instruction::ClaimBadge { achievement },
+    )
+}
+
+/// Withdraws lamports to `receiver`, or with `fee` set, tokens of that mint to
+/// `fee.token_account`, which must be owned by the authority.
+pub fn withdraw_fees(authority: &Pubkey, receiver: &Pubkey, amount: u64, fee: Option<TokenFee>) -> Instruction {
+    build(
+        soddle::accounts::WithdrawFees {
+            metrics: pda::game_metrics().0,
+            fee_vault: pda::fee_vault().0,
+            treasury_ledger: pda::treasury_ledger(fee.map(|fee| fee.mint)).0,
+            receiver: *receiver,
+            authority: *authority,
+            system_program: system_program::ID,
+            fee_mint: fee.map(|fee| fee.mint),
+            token_vault: fee.map(|fee| pda::token_vault(&fee.mint).0),
+            token_metrics: fee.map(|fee| pda::token_metrics(&fee.mint).0),
+            receiver_token_account: fee.map(|fee| fee.token_account),
+            token_program: fee.map(|fee| fee.token_program),
+        },
+        soddle::instruction::WithdrawFees { amount },
+    )
+}
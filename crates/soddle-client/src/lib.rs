@@ -0,0 +1,54 @@
+//! Off-chain helpers for the soddle program: PDA derivation, instruction builders,
+//! typed account decoding, event decoding, `SoddleError` decoding and loading of
+//! transactions that carry events. The program's pure scoring rules are re-exported as
+//! `scoring`, its fee arithmetic as `fees`, its achievement definitions as `achievements`
+//! and its Merkle allowlists as `allowlist`.
+
+pub mod accounts;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+pub mod transactions;
+
+pub use soddle::achievements;
+pub use soddle::allowlist;
+pub use soddle::fees;
+pub use soddle::scoring;
+pub use soddle::ID as PROGRAM_ID;
+
+#[derive(Debug)]
+pub enum ClientError {
+    Anchor(anchor_lang::error::Error),
+    #[cfg(feature = "rpc")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    WrongOwner(anchor_lang::prelude::Pubkey),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Anchor(err) => write!(f, "{err}"),
+            #[cfg(feature = "rpc")]
+            ClientError::Rpc(err) => write!(f, "{err}"),
+            ClientError::WrongOwner(address) => {
+                write!(f, "account {address} is not owned by the soddle program")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(err: anchor_lang::error::Error) -> Self {
+        ClientError::Anchor(err)
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(err))
+    }
+}
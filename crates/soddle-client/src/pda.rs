@@ -0,0 +1,161 @@
+use anchor_lang::prelude::Pubkey;
+use soddle::achievements::Achievement;
+
+pub const GAME_STATE_SEED: &[u8] = b"game_state";
+pub const COMPETITION_SEED: &[u8] = b"competition";
+pub const KOL_SEED: &[u8] = b"kol";
+pub const GAME_SESSION_SEED: &[u8] = b"game_session";
+pub const GUESS_VERIFIER_SEED: &[u8] = b"guess_verifier";
+pub const PRACTICE_VERIFIER_SEED: &[u8] = b"practice_verifier";
+pub const GAME_METRICS_SEED: &[u8] = b"game_metrics";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const GAME_CONFIG_SEED: &[u8] = b"game_config";
+pub const PLAYER_PROFILE_SEED: &[u8] = b"player_profile";
+pub const BADGE_MINT_SEED: &[u8] = b"badge_mint";
+pub const TOKEN_VAULT_SEED: &[u8] = b"token_vault";
+pub const TOKEN_METRICS_SEED: &[u8] = b"token_metrics";
+pub const TREASURY_LEDGER_SEED: &[u8] = b"treasury_ledger";
+pub const PRIZE_POOL_LEDGER_SEED: &[u8] = b"prize_pool_ledger";
+pub const KOL_LEDGER_SEED: &[u8] = b"kol_ledger";
+pub const REFERRER_LEDGER_SEED: &[u8] = b"referrer_ledger";
+pub const REFERRAL_SEED: &[u8] = b"referral";
+pub const VOUCHER_BITMAP_SEED: &[u8] = b"voucher_bitmap";
+pub const SEASON_PASS_SEED: &[u8] = b"season_pass";
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// `["game_state", payer]`
+pub fn game_state(payer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_STATE_SEED, payer.as_ref()], &soddle::ID)
+}
+
+/// `["competition", game_state, id (u64 LE)]`
+pub fn competition(game_state: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COMPETITION_SEED, game_state.as_ref(), &id.to_le_bytes()],
+        &soddle::ID,
+    )
+}
+
+/// `["kol", id (u64 LE)]`
+pub fn kol(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[KOL_SEED, &id.to_le_bytes()], &soddle::ID)
+}
+
+/// `["game_session", player, competition_id (u64 LE), start_time (i64 LE)]`
+pub fn game_session(player: &Pubkey, competition_id: u64, start_time: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            GAME_SESSION_SEED,
+            player.as_ref(),
+            &competition_id.to_le_bytes(),
+            &start_time.to_le_bytes(),
+        ],
+        &soddle::ID,
+    )
+}
+
+/// `["guess_verifier", game_session]`
+pub fn guess_verifier(game_session: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GUESS_VERIFIER_SEED, game_session.as_ref()], &soddle::ID)
+}
+
+/// `["practice_verifier", game_session]`, the verifier of a practice session.
+pub fn practice_verifier(game_session: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRACTICE_VERIFIER_SEED, game_session.as_ref()], &soddle::ID)
+}
+
+/// The verifier of `game_session`, whichever kind of session it is.
+pub fn verifier(game_session: &Pubkey, is_practice: bool) -> (Pubkey, u8) {
+    if is_practice {
+        practice_verifier(game_session)
+    } else {
+        guess_verifier(game_session)
+    }
+}
+
+/// `["game_metrics"]`
+pub fn game_metrics() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_METRICS_SEED], &soddle::ID)
+}
+
+/// `["fee_vault"]`
+pub fn fee_vault() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_VAULT_SEED], &soddle::ID)
+}
+
+/// `["game_config"]`
+pub fn game_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_CONFIG_SEED], &soddle::ID)
+}
+
+/// `["player_profile", player]`
+pub fn player_profile(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLAYER_PROFILE_SEED, player.as_ref()], &soddle::ID)
+}
+
+/// `["badge_mint", achievement as u8]`, a Token-2022 mint.
+pub fn badge_mint(achievement: Achievement) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BADGE_MINT_SEED, &achievement.seed()], &soddle::ID)
+}
+
+/// `["token_vault", mint]`, the fee vault's token account for `mint`.
+pub fn token_vault(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOKEN_VAULT_SEED, mint.as_ref()], &soddle::ID)
+}
+
+/// `["token_metrics", mint]`
+pub fn token_metrics(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOKEN_METRICS_SEED, mint.as_ref()], &soddle::ID)
+}
+
+/// `["treasury_ledger", fee_vault, mint]`; lamport ledgers use the default pubkey as mint.
+pub fn treasury_ledger(mint: Option<Pubkey>) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TREASURY_LEDGER_SEED, fee_vault().0.as_ref(), mint.unwrap_or_default().as_ref()],
+        &soddle::ID,
+    )
+}
+
+/// `["prize_pool_ledger", competition, mint]`
+pub fn prize_pool_ledger(competition: &Pubkey, mint: Option<Pubkey>) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PRIZE_POOL_LEDGER_SEED, competition.as_ref(), mint.unwrap_or_default().as_ref()],
+        &soddle::ID,
+    )
+}
+
+/// `["kol_ledger", kol, mint]`
+pub fn kol_ledger(kol: &Pubkey, mint: Option<Pubkey>) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[KOL_LEDGER_SEED, kol.as_ref(), mint.unwrap_or_default().as_ref()],
+        &soddle::ID,
+    )
+}
+
+/// `["referrer_ledger", referrer, mint]`
+pub fn referrer_ledger(referrer: &Pubkey, mint: Option<Pubkey>) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFERRER_LEDGER_SEED, referrer.as_ref(), mint.unwrap_or_default().as_ref()],
+        &soddle::ID,
+    )
+}
+
+/// `["referral", player]`
+pub fn referral(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRAL_SEED, player.as_ref()], &soddle::ID)
+}
+
+/// `["voucher_bitmap", page]`, where a voucher's page is `nonce / VOUCHERS_PER_BITMAP`.
+pub fn voucher_bitmap(page: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOUCHER_BITMAP_SEED, &page.to_le_bytes()], &soddle::ID)
+}
+
+/// `["season_pass", player]`
+pub fn season_pass(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEASON_PASS_SEED, player.as_ref()], &soddle::ID)
+}
+
+/// `["__event_authority"]`, the signer of the program's self-CPI event instructions.
+pub fn event_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &soddle::ID)
+}
@@ -0,0 +1,139 @@
+#[cfg(feature = "rpc")]
+use anchor_lang::prelude::Pubkey;
+use serde_json::Value;
+#[cfg(feature = "rpc")]
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+#[cfg(feature = "rpc")]
+use solana_client::rpc_config::RpcTransactionConfig;
+#[cfg(feature = "rpc")]
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::events::{parse_logs, SoddleEvent, UnsupportedVersion};
+
+/// The parts of a `getTransaction` response needed to recover soddle events.
+pub struct RawTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub logs: Vec<String>,
+    /// Data of the inner instructions that invoked the soddle program, in execution order.
+    /// `emit_cpi!` events are delivered through these.
+    pub program_inner_instructions: Vec<Vec<u8>>,
+}
+
+impl RawTransaction {
+    /// Accepts a `getTransaction` result in JSON encoding, optionally wrapped in the RPC envelope.
+    /// Failed transactions are dropped: their state changes (and events) were rolled back.
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let value = value.get("result").unwrap_or(value);
+        let meta = value.get("meta")?;
+        if meta.get("err").is_some_and(|err| !err.is_null()) {
+            return None;
+        }
+
+        let signature = value
+            .pointer("/transaction/signatures/0")?
+            .as_str()?
+            .to_string();
+        let slot = value.get("slot")?.as_u64()?;
+        let logs = meta
+            .get("logMessages")?
+            .as_array()?
+            .iter()
+            .filter_map(|line| line.as_str().map(str::to_string))
+            .collect();
+        let program_inner_instructions = program_inner_instructions(value);
+
+        Some(RawTransaction {
+            signature,
+            slot,
+            logs,
+            program_inner_instructions,
+        })
+    }
+
+    /// The soddle events of the transaction, in emission order.
+    pub fn events(&self) -> Result<Vec<SoddleEvent>, UnsupportedVersion> {
+        let mut events = Vec::new();
+        for data in &self.program_inner_instructions {
+            events.extend(SoddleEvent::decode_cpi(data)?);
+        }
+        // Transactions from before the switch to `emit_cpi!` only carry their events in the logs.
+        if events.is_empty() {
+            parse_logs(&self.logs)
+        } else {
+            Ok(events)
+        }
+    }
+}
+
+fn program_inner_instructions(value: &Value) -> Vec<Vec<u8>> {
+    let program_id = crate::PROGRAM_ID.to_string();
+
+    // Versioned transactions index into the static keys followed by the lookup-table keys.
+    let mut keys: Vec<&str> = Vec::new();
+    for pointer in [
+        "/transaction/message/accountKeys",
+        "/meta/loadedAddresses/writable",
+        "/meta/loadedAddresses/readonly",
+    ] {
+        if let Some(array) = value.pointer(pointer).and_then(Value::as_array) {
+            keys.extend(array.iter().filter_map(Value::as_str));
+        }
+    }
+
+    let Some(groups) = value.pointer("/meta/innerInstructions").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+
+    groups
+        .iter()
+        .filter_map(|group| group.get("instructions").and_then(Value::as_array))
+        .flatten()
+        .filter(|ix| {
+            ix.get("programIdIndex")
+                .and_then(Value::as_u64)
+                .and_then(|index| keys.get(index as usize))
+                == Some(&program_id.as_str())
+        })
+        .filter_map(|ix| bs58::decode(ix.get("data")?.as_str()?).into_vec().ok())
+        .collect()
+}
+
+/// Reads a file holding either a single transaction or an array of them.
+pub fn read_file(path: &str) -> Result<Vec<RawTransaction>, Box<dyn std::error::Error>> {
+    let value: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let transactions = match value {
+        Value::Array(items) => items.iter().filter_map(RawTransaction::from_json).collect(),
+        other => RawTransaction::from_json(&other).into_iter().collect(),
+    };
+    Ok(transactions)
+}
+
+/// Fetches the most recent `limit` transactions that touched `address`, oldest first.
+#[cfg(feature = "rpc")]
+pub fn fetch_rpc(url: &str, address: &Pubkey, limit: usize) -> Result<Vec<RawTransaction>, Box<dyn std::error::Error>> {
+    let client = RpcClient::new(url.to_string());
+    let signatures = client.get_signatures_for_address_with_config(
+        address,
+        GetConfirmedSignaturesForAddress2Config {
+            limit: Some(limit),
+            ..Default::default()
+        },
+    )?;
+
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        max_supported_transaction_version: Some(0),
+        ..Default::default()
+    };
+
+    let mut transactions = Vec::new();
+    for status in signatures.iter().rev() {
+        let signature = status.signature.parse()?;
+        let transaction = client.get_transaction_with_config(&signature, config)?;
+        if let Some(raw) = RawTransaction::from_json(&serde_json::to_value(&transaction)?) {
+            transactions.push(raw);
+        }
+    }
+    Ok(transactions)
+}
@@ -0,0 +1,31 @@
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use soddle_client::errors::decode_error_code;
+
+const PROGRAM_ERRORS: &str = include_str!("../../../programs/soddle/src/errors.rs");
+
+/// Variant names of `SoddleError` in declaration order, read from the program's source.
+fn declared_errors() -> Vec<&'static str> {
+    let mut lines = PROGRAM_ERRORS.lines().map(str::trim);
+    let mut names = Vec::new();
+    while let Some(line) = lines.next() {
+        if line.starts_with("#[msg(") {
+            let variant = lines.next().expect("a variant after its message");
+            names.push(variant.trim_end_matches(','));
+        }
+    }
+    names
+}
+
+#[test]
+fn error_codes_follow_the_declaration_order() {
+    let declared = declared_errors();
+    assert!(!declared.is_empty());
+
+    for (index, name) in declared.iter().enumerate() {
+        let code = ERROR_CODE_OFFSET + index as u32;
+        let error = decode_error_code(code).unwrap_or_else(|| panic!("{name} is missing from SODDLE_ERRORS"));
+        assert_eq!(format!("{error:?}"), *name);
+        assert_eq!(u32::from(error), code);
+    }
+    assert!(decode_error_code(ERROR_CODE_OFFSET + declared.len() as u32).is_none());
+}
@@ -0,0 +1,45 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use soddle_client::events::{NewHighScore, SoddleEvent, UnsupportedVersion, EVENT_SCHEMA_VERSION};
+
+fn high_score(version: u8) -> Vec<u8> {
+    NewHighScore {
+        version,
+        session: Pubkey::new_unique(),
+        competition_id: 1,
+        game_type: 1,
+        player: Pubkey::new_unique(),
+        score: 1_000,
+        timestamp: 0,
+    }
+    .data()
+}
+
+#[test]
+fn current_events_decode() {
+    let Ok(Some(SoddleEvent::NewHighScore(event))) = SoddleEvent::decode(&high_score(EVENT_SCHEMA_VERSION)) else {
+        panic!("expected a NewHighScore");
+    };
+    assert_eq!(event.score, 1_000);
+
+    let cpi = [&EVENT_IX_TAG_LE[..], &high_score(EVENT_SCHEMA_VERSION)].concat();
+    assert!(matches!(SoddleEvent::decode_cpi(&cpi), Ok(Some(SoddleEvent::NewHighScore(_)))));
+}
+
+#[test]
+fn other_schema_versions_are_reported() {
+    let version = EVENT_SCHEMA_VERSION - 1;
+    let unsupported = UnsupportedVersion { event: "NewHighScore", version };
+
+    assert_eq!(SoddleEvent::decode(&high_score(version)).err(), Some(unsupported));
+    let cpi = [&EVENT_IX_TAG_LE[..], &high_score(version)].concat();
+    assert_eq!(SoddleEvent::decode_cpi(&cpi).err(), Some(unsupported));
+}
+
+#[test]
+fn other_data_is_not_an_event() {
+    assert!(matches!(SoddleEvent::decode(&[7; 32]), Ok(None)));
+    assert!(matches!(SoddleEvent::decode(&[]), Ok(None)));
+    assert!(matches!(SoddleEvent::decode_cpi(&high_score(EVENT_SCHEMA_VERSION)), Ok(None)));
+}
@@ -0,0 +1,4 @@
+//! Indexes soddle program events into SQLite. Every event is applied at most once, keyed by
+//! the signature of its transaction and its position among the transaction's events.
+
+pub mod store;
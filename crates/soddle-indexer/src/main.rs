@@ -0,0 +1,50 @@
+use soddle_client::transactions::{fetch_rpc, read_file, RawTransaction};
+use soddle_indexer::store::Store;
+
+const USAGE: &str = "usage: soddle-indexer --db <path> [--rpc <url>] [--limit <n>] [transaction.json ...]";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut db = None;
+    let mut rpc = None;
+    let mut limit = 1000;
+    let mut files = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--db" => db = args.next(),
+            "--rpc" => rpc = args.next(),
+            "--limit" => limit = args.next().ok_or(USAGE)?.parse()?,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ => files.push(arg),
+        }
+    }
+
+    let db = db.ok_or(USAGE)?;
+    if files.is_empty() && rpc.is_none() {
+        return Err(USAGE.into());
+    }
+
+    let mut transactions: Vec<RawTransaction> = Vec::new();
+    for path in &files {
+        transactions.extend(read_file(path)?);
+    }
+    if let Some(url) = rpc {
+        transactions.extend(fetch_rpc(&url, &soddle_client::PROGRAM_ID, limit)?);
+    }
+    transactions.sort_by_key(|tx| tx.slot);
+
+    let mut store = Store::open(&db)?;
+    let mut applied = 0;
+    for tx in &transactions {
+        // Stop at events this build can't read rather than index around them
+        let events = tx.events().map_err(|err| format!("{}: {err}", tx.signature))?;
+        applied += store.ingest(&tx.signature, tx.slot, &events)?;
+    }
+
+    println!("indexed {} transactions, {applied} new events", transactions.len());
+    Ok(())
+}
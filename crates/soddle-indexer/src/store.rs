@@ -0,0 +1,304 @@
+use rusqlite::{params, Connection, Transaction};
+use soddle_client::events::*;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS processed_events (
+    signature   TEXT    NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    name        TEXT    NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE TABLE IF NOT EXISTS attempts (
+    signature   TEXT    NOT NULL,
+    event_index INTEGER NOT NULL,
+    session     TEXT    NOT NULL,
+    competition_id INTEGER NOT NULL,
+    game_type   INTEGER NOT NULL,
+    player      TEXT    NOT NULL,
+    kol         TEXT    NOT NULL,
+    attempt     INTEGER NOT NULL,
+    started_at  INTEGER NOT NULL,
+    fee_mint    TEXT,
+    fee_paid    INTEGER NOT NULL,
+    is_bonus    INTEGER NOT NULL,
+    is_practice INTEGER NOT NULL,
+    final_score INTEGER,
+    total_guesses INTEGER,
+    was_correct INTEGER,
+    reason      TEXT,
+    completed_at INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS attempts_by_session ON attempts (session, attempt);
+CREATE TABLE IF NOT EXISTS guesses (
+    signature     TEXT    NOT NULL,
+    event_index   INTEGER NOT NULL,
+    session       TEXT    NOT NULL,
+    competition_id INTEGER NOT NULL,
+    game_type     INTEGER NOT NULL,
+    player        TEXT    NOT NULL,
+    attempt_index INTEGER NOT NULL,
+    guess_number  INTEGER NOT NULL,
+    current_score INTEGER NOT NULL,
+    was_correct   INTEGER NOT NULL,
+    time_deduction INTEGER NOT NULL,
+    wrong_guess_penalty INTEGER NOT NULL,
+    speed_bonus   INTEGER NOT NULL,
+    timestamp     INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS guesses_by_session ON guesses (session, attempt_index);
+CREATE TABLE IF NOT EXISTS sessions (
+    signature      TEXT    NOT NULL,
+    event_index    INTEGER NOT NULL,
+    session        TEXT    NOT NULL,
+    player         TEXT    NOT NULL,
+    competition_id INTEGER NOT NULL,
+    game_type      INTEGER NOT NULL,
+    total_attempts INTEGER NOT NULL,
+    best_score     INTEGER NOT NULL,
+    total_score    INTEGER NOT NULL,
+    deposit_refunded INTEGER NOT NULL,
+    refund_amount  INTEGER NOT NULL,
+    ended_at       INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS sessions_by_session ON sessions (session);
+CREATE INDEX IF NOT EXISTS sessions_by_competition ON sessions (competition_id);
+CREATE TABLE IF NOT EXISTS competitions (
+    competition_id INTEGER PRIMARY KEY,
+    sessions_ended INTEGER NOT NULL,
+    best_score     INTEGER NOT NULL,
+    best_player    TEXT,
+    total_refunded INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS streaks (
+    signature      TEXT    NOT NULL,
+    event_index    INTEGER NOT NULL,
+    session        TEXT    NOT NULL,
+    player         TEXT    NOT NULL,
+    day            INTEGER NOT NULL,
+    current_streak INTEGER NOT NULL,
+    longest_streak INTEGER NOT NULL,
+    bonus_bps      INTEGER NOT NULL,
+    timestamp      INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS streaks_by_player ON streaks (player, day);
+CREATE TABLE IF NOT EXISTS achievements (
+    signature      TEXT    NOT NULL,
+    event_index    INTEGER NOT NULL,
+    session        TEXT    NOT NULL,
+    player         TEXT    NOT NULL,
+    achievement    TEXT    NOT NULL,
+    unlocked_at    INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS achievements_by_player ON achievements (player);
+CREATE TABLE IF NOT EXISTS payouts (
+    signature      TEXT    NOT NULL,
+    event_index    INTEGER NOT NULL,
+    session        TEXT    NOT NULL,
+    player         TEXT    NOT NULL,
+    competition_id INTEGER NOT NULL,
+    amount         INTEGER NOT NULL,
+    kind           TEXT    NOT NULL,
+    timestamp      INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+";
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Store { conn })
+    }
+
+    /// Applies every event of one transaction atomically. Events already recorded under the same
+    /// `(signature, event_index)` are skipped, so re-ingesting a transaction is a no-op.
+    pub fn ingest(&mut self, signature: &str, slot: u64, events: &[SoddleEvent]) -> rusqlite::Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut applied = 0;
+
+        for (index, event) in events.iter().enumerate() {
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO processed_events (signature, event_index, slot, name)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![signature, index as i64, slot as i64, event.name()],
+            )?;
+            if inserted == 0 {
+                continue;
+            }
+            apply(&tx, signature, index as i64, event)?;
+            applied += 1;
+        }
+
+        tx.commit()?;
+        Ok(applied)
+    }
+}
+
+fn apply(tx: &Transaction, signature: &str, index: i64, event: &SoddleEvent) -> rusqlite::Result<()> {
+    match event {
+        SoddleEvent::GameSessionStarted(e) => {
+            tx.execute(
+                "INSERT INTO attempts (signature, event_index, session, competition_id, game_type,
+                     player, kol, attempt, started_at, fee_mint, fee_paid, is_bonus, is_practice)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    signature,
+                    index,
+                    e.session.to_string(),
+                    e.competition_id as i64,
+                    e.game_type,
+                    e.player.to_string(),
+                    e.kol.to_string(),
+                    e.attempt,
+                    e.timestamp,
+                    e.fee_mint.map(|mint| mint.to_string()),
+                    e.fee_paid as i64,
+                    e.is_bonus,
+                    e.is_practice
+                ],
+            )?;
+        }
+        SoddleEvent::GuessAttempted(e) => {
+            tx.execute(
+                "INSERT INTO guesses (signature, event_index, session, competition_id, game_type,
+                     player, attempt_index, guess_number, current_score, was_correct,
+                     time_deduction, wrong_guess_penalty, speed_bonus, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                params![
+                    signature,
+                    index,
+                    e.session.to_string(),
+                    e.competition_id as i64,
+                    e.game_type,
+                    e.player.to_string(),
+                    e.attempt_index,
+                    e.guess_number,
+                    e.current_score,
+                    e.was_correct,
+                    e.time_deduction,
+                    e.wrong_guess_penalty,
+                    e.speed_bonus,
+                    e.timestamp
+                ],
+            )?;
+        }
+        SoddleEvent::AttemptCompleted(e) => {
+            // `attempt_index` is zero based while `GameSessionStarted::attempt` counts from one.
+            // Session PDAs can be recreated after closing, so pick the latest matching start.
+            tx.execute(
+                "UPDATE attempts
+                 SET final_score = ?1, total_guesses = ?2, was_correct = ?3, reason = ?4, completed_at = ?5
+                 WHERE rowid = (
+                     SELECT rowid FROM attempts
+                     WHERE session = ?6 AND attempt = ?7 AND started_at <= ?5
+                     ORDER BY started_at DESC LIMIT 1
+                 )",
+                params![
+                    e.final_score,
+                    e.total_guesses,
+                    e.was_correct,
+                    format!("{:?}", e.reason),
+                    e.timestamp,
+                    e.session.to_string(),
+                    e.attempt_index as i64 + 1
+                ],
+            )?;
+        }
+        SoddleEvent::GameSessionEnded(e) => {
+            tx.execute(
+                "INSERT INTO sessions (signature, event_index, session, player, competition_id, game_type,
+                     total_attempts, best_score, total_score, deposit_refunded, refund_amount, ended_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    signature,
+                    index,
+                    e.session.to_string(),
+                    e.player.to_string(),
+                    e.competition_id as i64,
+                    e.game_type,
+                    e.total_attempts,
+                    e.best_score,
+                    e.total_score,
+                    e.deposit_refunded,
+                    e.refund_amount as i64,
+                    e.timestamp
+                ],
+            )?;
+            // Rebuilt from `sessions` rather than incremented, so the totals never count an
+            // ended session twice and the best player doesn't depend on the ingestion order
+            tx.execute(
+                "INSERT OR REPLACE INTO competitions (competition_id, sessions_ended, best_score, best_player, total_refunded)
+                 SELECT competition_id, COUNT(*), MAX(total_score),
+                     (SELECT player FROM sessions AS best
+                      WHERE best.competition_id = ended.competition_id
+                      ORDER BY total_score DESC, ended_at, signature, event_index LIMIT 1),
+                     SUM(refund_amount)
+                 FROM sessions AS ended
+                 WHERE competition_id = ?1
+                 GROUP BY competition_id",
+                // Ranked by the final score, streak bonus included, like the on-chain high score
+                params![e.competition_id as i64],
+            )?;
+            if e.deposit_refunded {
+                tx.execute(
+                    "INSERT INTO payouts (signature, event_index, session, player, competition_id, amount, kind, timestamp)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'refund', ?7)",
+                    params![
+                        signature,
+                        index,
+                        e.session.to_string(),
+                        e.player.to_string(),
+                        e.competition_id as i64,
+                        e.refund_amount as i64,
+                        e.timestamp
+                    ],
+                )?;
+            }
+        }
+        SoddleEvent::StreakUpdated(e) => {
+            tx.execute(
+                "INSERT INTO streaks (signature, event_index, session, player, day, current_streak,
+                     longest_streak, bonus_bps, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    signature,
+                    index,
+                    e.session.to_string(),
+                    e.player.to_string(),
+                    e.day,
+                    e.current_streak,
+                    e.longest_streak,
+                    e.bonus_bps,
+                    e.timestamp
+                ],
+            )?;
+        }
+        SoddleEvent::AchievementUnlocked(e) => {
+            tx.execute(
+                "INSERT INTO achievements (signature, event_index, session, player, achievement, unlocked_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    signature,
+                    index,
+                    e.session.to_string(),
+                    e.player.to_string(),
+                    format!("{:?}", e.achievement),
+                    e.timestamp
+                ],
+            )?;
+        }
+        // Both are derivable from the rows above; they are still recorded in `processed_events`.
+        SoddleEvent::GameSessionCompleted(_) | SoddleEvent::NewHighScore(_) => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::Pubkey;
+use rusqlite::Connection;
+use soddle_client::events::*;
+use soddle_indexer::store::Store;
+
+const START: i64 = 1_700_000_000;
+const REFUND: u64 = 10_000_000;
+
+/// A fresh database file for one test, so tests can read back what `Store` wrote.
+fn database(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("soddle-indexer-{}-{name}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path.to_str().unwrap().to_string()
+}
+
+fn count(conn: &Connection, table: &str) -> i64 {
+    conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0)).unwrap()
+}
+
+fn streak(player: Pubkey, current_streak: u32) -> SoddleEvent {
+    SoddleEvent::StreakUpdated(StreakUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        session: Pubkey::new_unique(),
+        competition_id: 1,
+        game_type: 1,
+        player,
+        day: START / 86_400,
+        current_streak,
+        longest_streak: current_streak,
+        bonus_bps: 0,
+        timestamp: START,
+    })
+}
+
+fn ended(player: Pubkey, total_score: u32, timestamp: i64) -> SoddleEvent {
+    SoddleEvent::GameSessionEnded(GameSessionEnded {
+        version: EVENT_SCHEMA_VERSION,
+        session: Pubkey::new_unique(),
+        competition_id: 1,
+        game_type: 1,
+        player,
+        total_attempts: 1,
+        best_score: total_score,
+        total_score,
+        deposit_refunded: true,
+        refund_amount: REFUND,
+        timestamp,
+    })
+}
+
+fn competition(conn: &Connection) -> (i64, u32, String, i64) {
+    conn.query_row(
+        "SELECT sessions_ended, best_score, best_player, total_refunded FROM competitions WHERE competition_id = 1",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+    )
+    .unwrap()
+}
+
+#[test]
+fn ingesting_a_transaction_twice_changes_nothing() {
+    let path = database("twice");
+    let mut store = Store::open(&path).unwrap();
+    let player = Pubkey::new_unique();
+    let events = [streak(player, 1), ended(player, 900, START)];
+
+    assert_eq!(store.ingest("sig-1", 1, &events).unwrap(), 2);
+    assert_eq!(store.ingest("sig-1", 1, &events).unwrap(), 0);
+
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(count(&conn, "sessions"), 1);
+    assert_eq!(count(&conn, "streaks"), 1);
+    assert_eq!(count(&conn, "payouts"), 1);
+    assert_eq!(competition(&conn), (1, 900, player.to_string(), REFUND as i64));
+}
+
+#[test]
+fn every_event_of_a_transaction_gets_its_own_row() {
+    let path = database("batch");
+    let mut store = Store::open(&path).unwrap();
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let events = [
+        streak(first, 1),
+        ended(first, 800, START),
+        streak(second, 3),
+        ended(second, 950, START + 1),
+    ];
+
+    assert_eq!(store.ingest("sig-1", 1, &events).unwrap(), 4);
+
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(count(&conn, "sessions"), 2);
+    assert_eq!(count(&conn, "streaks"), 2);
+    assert_eq!(count(&conn, "payouts"), 2);
+    assert_eq!(competition(&conn), (2, 950, second.to_string(), 2 * REFUND as i64));
+}
+
+#[test]
+fn the_best_player_does_not_depend_on_ingestion_order() {
+    let path = database("order");
+    let mut store = Store::open(&path).unwrap();
+    let (earlier, later) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // Equal scores go to whoever ended first, even when their transaction is indexed last
+    store.ingest("sig-2", 2, &[ended(later, 900, START + 60)]).unwrap();
+    store.ingest("sig-1", 1, &[ended(earlier, 900, START)]).unwrap();
+
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(competition(&conn), (2, 900, earlier.to_string(), 2 * REFUND as i64));
+}
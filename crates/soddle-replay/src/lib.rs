@@ -0,0 +1,288 @@
+//! Re-scores a game session from its event stream with the program's own scoring code and
+//! reports every point where the recorded values diverge from the recomputed ones.
+//!
+//! The session is rebuilt as a `GameSession` and driven through the same methods
+//! `make_guess` and `end_game_session` use, so a divergence means the events disagree with
+//! the rules the program was built with (or with each other).
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use soddle::constants::*;
+use soddle::events::*;
+use soddle::scoring;
+use soddle::states::game_attempt::GameAttempt;
+use soddle::states::game_session::GameSession;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle_client::events::SoddleEvent;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// Position of the offending event among the session's events.
+    pub event_index: usize,
+    pub event: &'static str,
+    pub field: &'static str,
+    pub expected: String,
+    pub recorded: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "event #{} {}: {} recorded {}, expected {}",
+            self.event_index, self.event, self.field, self.recorded, self.expected
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub session: Pubkey,
+    /// Number of events that belonged to the session.
+    pub events: usize,
+    pub divergences: Vec<Divergence>,
+}
+
+impl Report {
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Replays the events of `session`, in emission order. Events of other sessions are skipped,
+/// as are `GameSessionCompleted`, `AchievementUnlocked` and `NewHighScore`, which depend on state
+/// outside the session.
+pub fn replay(session: &Pubkey, events: &[SoddleEvent]) -> Report {
+    let mut replayer = Replayer::default();
+    let mut count = 0;
+
+    for event in events.iter().filter(|event| event_session(event) == Some(session)) {
+        replayer.event_index = count;
+        replayer.event = event.name();
+        match event {
+            SoddleEvent::GameSessionStarted(e) => replayer.started(e),
+            SoddleEvent::GuessAttempted(e) => replayer.guessed(e),
+            SoddleEvent::AttemptCompleted(e) => replayer.attempt_completed(e),
+            SoddleEvent::StreakUpdated(e) => replayer.streak_bonus_bps = Some(e.bonus_bps),
+            SoddleEvent::GameSessionEnded(e) => replayer.ended(e),
+            SoddleEvent::GameSessionCompleted(_)
+            | SoddleEvent::AchievementUnlocked(_)
+            | SoddleEvent::NewHighScore(_) => {}
+        }
+        count += 1;
+    }
+
+    replayer.event_index = count;
+    replayer.event = "end of stream";
+    replayer.expect_no_pending_completion();
+
+    Report {
+        session: *session,
+        events: count,
+        divergences: replayer.divergences,
+    }
+}
+
+fn event_session(event: &SoddleEvent) -> Option<&Pubkey> {
+    match event {
+        SoddleEvent::GameSessionStarted(e) => Some(&e.session),
+        SoddleEvent::GuessAttempted(e) => Some(&e.session),
+        SoddleEvent::GameSessionCompleted(e) => Some(&e.session),
+        SoddleEvent::AttemptCompleted(e) => Some(&e.session),
+        SoddleEvent::GameSessionEnded(e) => Some(&e.session),
+        SoddleEvent::StreakUpdated(e) => Some(&e.session),
+        SoddleEvent::AchievementUnlocked(e) => Some(&e.session),
+        SoddleEvent::NewHighScore(e) => Some(&e.session),
+    }
+}
+
+fn reason_name(reason: &CompletionReason) -> &'static str {
+    match reason {
+        CompletionReason::CorrectGuess => "CorrectGuess",
+        CompletionReason::MaxGuesses => "MaxGuesses",
+        CompletionReason::TimeExpired => "TimeExpired",
+    }
+}
+
+#[derive(Default)]
+struct Replayer {
+    game_session: Option<GameSession>,
+    scoring_rule: ScoringRule,
+    score_model: ScoreModel,
+    /// Set when a guess completed the attempt and its `AttemptCompleted` is still due.
+    pending_completion: Option<(bool, &'static str)>,
+    /// Streak bonus announced right before `GameSessionEnded`; the streak itself spans sessions.
+    streak_bonus_bps: Option<u32>,
+    event_index: usize,
+    event: &'static str,
+    divergences: Vec<Divergence>,
+}
+
+impl Replayer {
+    fn diverge(&mut self, field: &'static str, expected: impl ToString, recorded: impl ToString) {
+        self.divergences.push(Divergence {
+            event_index: self.event_index,
+            event: self.event,
+            field,
+            expected: expected.to_string(),
+            recorded: recorded.to_string(),
+        });
+    }
+
+    fn check<T: PartialEq + ToString>(&mut self, field: &'static str, expected: T, recorded: T) {
+        if expected != recorded {
+            self.diverge(field, expected, recorded);
+        }
+    }
+
+    fn expect_no_pending_completion(&mut self) {
+        if let Some((_, reason)) = self.pending_completion.take() {
+            self.diverge("AttemptCompleted", reason, "missing");
+        }
+    }
+
+    /// The session and the index of its running attempt, if an attempt was started.
+    fn current_attempt(&mut self) -> Option<(&mut GameSession, usize)> {
+        let game_session = self.game_session.as_mut()?;
+        let index = game_session.current_attempt.checked_sub(1)? as usize;
+        Some((game_session, index))
+    }
+
+    fn started(&mut self, e: &GameSessionStarted) {
+        self.expect_no_pending_completion();
+
+        let game_session = self.game_session.get_or_insert_with(|| GameSession {
+            player: e.player,
+            start_time: e.timestamp,
+            total_score: 0,
+            deposit: 0,
+            fee_mint: e.fee_mint,
+            fee_split: Default::default(),
+            referrer: None,
+            kol: e.kol,
+            competition_id: e.competition_id,
+            competition: Pubkey::default(),  // Not in the events, and not needed to re-score
+            game_type: e.game_type,
+            is_practice: e.is_practice,
+            current_attempt: 0,
+            bonus_attempts: 0,
+            attempts: Default::default(),
+            bump: 0,
+        });
+        // Season passes and bought attempts go past MAX_DAILY_ATTEMPTS; the events don't
+        // record passes, so only the session's slots bound the attempts
+        if !game_session.can_start_new_attempt(MAX_SESSION_ATTEMPTS) {
+            let attempts = game_session.current_attempt;
+            self.diverge("attempt", format!("at most {MAX_SESSION_ATTEMPTS}"), attempts + 1);
+            return;
+        }
+
+        let index = game_session.current_attempt as usize;
+        game_session.attempts[index] = GameAttempt {
+            score: INITIAL_SCORE,
+            timestamp: e.timestamp,
+            is_bonus: e.is_bonus,
+            ..GameAttempt::default()
+        };
+        game_session.current_attempt += 1;
+        game_session.deposit += e.fee_paid;
+        let expected_attempt = game_session.current_attempt;
+
+        self.scoring_rule = e.scoring_rule;
+        self.score_model = e.score_model;
+        self.check("attempt", expected_attempt, e.attempt);
+    }
+
+    fn guessed(&mut self, e: &GuessAttempted) {
+        self.expect_no_pending_completion();
+        let (rule, model) = (self.scoring_rule, self.score_model);
+
+        let Some((game_session, index)) = self.current_attempt() else {
+            return self.diverge("attempt_index", "no attempt started", e.attempt_index);
+        };
+        let attempt = &game_session.attempts[index];
+        let completed = attempt.completed;
+        let timed_out = scoring::is_attempt_timed_out(attempt.timestamp, e.timestamp);
+        let deadline = attempt.timestamp.saturating_add(ATTEMPT_TIME_LIMIT);
+        let expected_guess_number = attempt.guesses + 1;
+
+        let breakdown = game_session.calculate_current_score(&rule, &model, e.timestamp, e.was_correct);
+        let attempt = &mut game_session.attempts[index];
+        attempt.guesses += 1;
+        attempt.score = breakdown.final_score;
+        let out_of_guesses = attempt.guesses >= MAX_GUESSES;
+        let out_of_time = scoring::is_attempt_timed_out(attempt.timestamp, e.timestamp);
+        if e.was_correct || out_of_guesses || out_of_time {
+            attempt.completed = true;
+            let reason = if e.was_correct {
+                "CorrectGuess"
+            } else if out_of_guesses {
+                "MaxGuesses"
+            } else {
+                "TimeExpired"
+            };
+            self.pending_completion = Some((e.was_correct, reason));
+        }
+
+        // The program rejects these guesses outright
+        if completed {
+            self.diverge("attempt", "an open attempt", "a completed attempt");
+        }
+        if timed_out {
+            self.diverge("timestamp", format!("before {deadline}"), e.timestamp);
+        }
+
+        self.check("attempt_index", index as u8, e.attempt_index);
+        self.check("guess_number", expected_guess_number, e.guess_number);
+        self.check("current_score", breakdown.final_score, e.current_score);
+        self.check("time_deduction", breakdown.time_deduction, e.time_deduction);
+        self.check("wrong_guess_penalty", breakdown.wrong_guess_deduction, e.wrong_guess_penalty);
+        self.check("speed_bonus", breakdown.bonus, e.speed_bonus);
+    }
+
+    fn attempt_completed(&mut self, e: &AttemptCompleted) {
+        let pending = self.pending_completion.take();
+        let Some((game_session, index)) = self.current_attempt() else {
+            return self.diverge("attempt_index", "no attempt started", e.attempt_index);
+        };
+        let (score, guesses) = (game_session.attempts[index].score, game_session.attempts[index].guesses);
+
+        let Some((was_correct, reason)) = pending else {
+            return self.diverge("attempt", "still running", "completed");
+        };
+        self.check("attempt_index", index as u8, e.attempt_index);
+        self.check("final_score", score, e.final_score);
+        self.check("total_guesses", guesses, e.total_guesses);
+        self.check("was_correct", was_correct, e.was_correct);
+        self.check("reason", reason, reason_name(&e.reason));
+    }
+
+    fn ended(&mut self, e: &GameSessionEnded) {
+        self.expect_no_pending_completion();
+        let Some(game_session) = self.game_session.as_ref() else {
+            // A session closed without attempts never emitted a start
+            self.check("total_attempts", 0, e.total_attempts);
+            return;
+        };
+
+        let can_end = game_session.can_end_session();
+        let total_attempts = game_session.current_attempt;
+        let best_score = game_session.best_score();
+        let total_score = game_session.final_score(self.streak_bonus_bps.unwrap_or(0));
+        let refunded = game_session.should_refund_deposit();
+        let refund_amount = if refunded { game_session.deposit } else { 0 };
+
+        if !can_end {
+            self.diverge("attempt", "completed before the session ends", "still running");
+        }
+        self.check("total_attempts", total_attempts, e.total_attempts);
+        self.check("best_score", best_score, e.best_score);
+        self.check("deposit_refunded", refunded, e.deposit_refunded);
+        self.check("refund_amount", refund_amount, e.refund_amount);
+        if self.streak_bonus_bps.is_some() {
+            self.check("total_score", total_score, e.total_score);
+        }
+    }
+}
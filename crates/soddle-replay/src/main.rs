@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use soddle_client::transactions::{fetch_rpc, read_file, RawTransaction};
+
+const USAGE: &str = "usage: soddle-replay <session> [--rpc <url>] [--limit <n>] [transaction.json ...]";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut session = None;
+    let mut rpc = None;
+    let mut limit = 1000;
+    let mut files = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rpc" => rpc = args.next(),
+            "--limit" => limit = args.next().ok_or(USAGE)?.parse()?,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ if session.is_none() => session = Some(Pubkey::from_str(&arg)?),
+            _ => files.push(arg),
+        }
+    }
+
+    let session = session.ok_or(USAGE)?;
+    if files.is_empty() && rpc.is_none() {
+        return Err(USAGE.into());
+    }
+
+    let mut transactions: Vec<RawTransaction> = Vec::new();
+    for path in &files {
+        transactions.extend(read_file(path)?);
+    }
+    if let Some(url) = rpc {
+        // The session PDA is closed when the session ends, but its signatures stay queryable
+        transactions.extend(fetch_rpc(&url, &session, limit)?);
+    }
+    transactions.sort_by_key(|tx| tx.slot);
+
+    // Events this build can't read would leave gaps in the replay, so they stop it
+    let mut events = Vec::new();
+    for tx in &transactions {
+        events.extend(tx.events().map_err(|err| format!("{}: {err}", tx.signature))?);
+    }
+    let report = soddle_replay::replay(&session, &events);
+
+    for divergence in &report.divergences {
+        println!("{divergence}");
+    }
+    println!(
+        "replayed {} events of {session}: {} divergences",
+        report.events,
+        report.divergences.len()
+    );
+
+    if !report.is_consistent() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
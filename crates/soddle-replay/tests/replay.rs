@@ -0,0 +1,177 @@
+use anchor_lang::prelude::Pubkey;
+use soddle::constants::*;
+use soddle::events::*;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle_client::events::SoddleEvent;
+use soddle_replay::replay;
+
+const START: i64 = 1_700_000_000;
+
+struct Stream {
+    session: Pubkey,
+    player: Pubkey,
+    events: Vec<SoddleEvent>,
+}
+
+impl Stream {
+    fn new() -> Self {
+        Stream {
+            session: Pubkey::new_unique(),
+            player: Pubkey::new_unique(),
+            events: Vec::new(),
+        }
+    }
+
+    fn start(&mut self, attempt: u8, timestamp: i64) -> &mut Self {
+        self.events.push(SoddleEvent::GameSessionStarted(GameSessionStarted {
+            version: EVENT_SCHEMA_VERSION,
+            session: self.session,
+            competition_id: 1,
+            game_type: 1,
+            player: self.player,
+            kol: Pubkey::new_unique(),
+            attempt,
+            timestamp,
+            scoring_rule: ScoringRule::default(),
+            score_model: ScoreModel::default(),
+            fee_mint: None,
+            fee_paid: GAME_FEE,
+            is_bonus: false,
+            is_practice: false,
+        }));
+        self
+    }
+
+    fn guess(&mut self, guess_number: u32, current_score: u32, was_correct: bool, timestamp: i64) -> &mut Self {
+        let time_deduction = ((timestamp - START) / TIME_PENALTY_INTERVAL) as u32 * TIME_PENALTY;
+        self.events.push(SoddleEvent::GuessAttempted(GuessAttempted {
+            version: EVENT_SCHEMA_VERSION,
+            session: self.session,
+            competition_id: 1,
+            game_type: 1,
+            player: self.player,
+            attempt_index: 0,
+            guess_number,
+            current_score,
+            was_correct,
+            timestamp,
+            time_deduction,
+            wrong_guess_penalty: if was_correct { 0 } else { WRONG_GUESS_PENALTY },
+            speed_bonus: 0,
+        }));
+        self
+    }
+
+    fn complete(&mut self, final_score: u32, total_guesses: u32, timestamp: i64) -> &mut Self {
+        self.events.push(SoddleEvent::AttemptCompleted(AttemptCompleted {
+            version: EVENT_SCHEMA_VERSION,
+            session: self.session,
+            competition_id: 1,
+            game_type: 1,
+            player: self.player,
+            attempt_index: 0,
+            final_score,
+            total_guesses,
+            was_correct: true,
+            timestamp,
+            reason: CompletionReason::CorrectGuess,
+        }));
+        self
+    }
+
+    fn end(&mut self, best_score: u32, deposit_refunded: bool, timestamp: i64) -> &mut Self {
+        self.events.push(SoddleEvent::GameSessionEnded(GameSessionEnded {
+            version: EVENT_SCHEMA_VERSION,
+            session: self.session,
+            competition_id: 1,
+            game_type: 1,
+            player: self.player,
+            total_attempts: 1,
+            best_score,
+            total_score: 0,
+            deposit_refunded,
+            refund_amount: if deposit_refunded { GAME_FEE } else { 0 },
+            timestamp,
+        }));
+        self
+    }
+}
+
+/// One wrong guess, then a solve two intervals in.
+fn honest_session() -> Stream {
+    let mut stream = Stream::new();
+    let solved_at = START + 2 * TIME_PENALTY_INTERVAL;
+    let score = INITIAL_SCORE - 2 * TIME_PENALTY;
+    stream
+        .start(1, START)
+        .guess(1, INITIAL_SCORE - WRONG_GUESS_PENALTY, false, START)
+        .guess(2, score, true, solved_at)
+        .complete(score, 2, solved_at)
+        .end(score, true, solved_at + 1);
+    stream
+}
+
+#[test]
+fn honest_sessions_replay_cleanly() {
+    let stream = honest_session();
+    let report = replay(&stream.session, &stream.events);
+    assert_eq!(report.events, 5);
+    assert!(report.is_consistent(), "{:?}", report.divergences);
+}
+
+#[test]
+fn events_of_other_sessions_are_ignored() {
+    let stream = honest_session();
+    let mut other = Stream::new();
+    other.start(2, START);
+
+    let mut events = other.events;
+    events.extend(stream.events);
+    let report = replay(&stream.session, &events);
+    assert_eq!(report.events, 5);
+    assert!(report.is_consistent());
+}
+
+#[test]
+fn inflated_scores_are_reported() {
+    let mut stream = honest_session();
+    if let SoddleEvent::GuessAttempted(guess) = &mut stream.events[2] {
+        guess.current_score += 100;
+    }
+
+    let report = replay(&stream.session, &stream.events);
+    assert_eq!(report.divergences.len(), 1);
+    let divergence = &report.divergences[0];
+    assert_eq!(divergence.event_index, 2);
+    assert_eq!(divergence.field, "current_score");
+    assert_eq!(divergence.expected, (INITIAL_SCORE - 2 * TIME_PENALTY).to_string());
+}
+
+#[test]
+fn late_guesses_and_missing_completions_are_reported() {
+    let mut stream = Stream::new();
+    let late = START + ATTEMPT_TIME_LIMIT;
+    let score = INITIAL_SCORE - (ATTEMPT_TIME_LIMIT / TIME_PENALTY_INTERVAL) as u32 * TIME_PENALTY;
+    stream.start(1, START).guess(1, score, true, late);
+
+    let report = replay(&stream.session, &stream.events);
+    let fields: Vec<_> = report.divergences.iter().map(|d| (d.event, d.field)).collect();
+    assert_eq!(
+        fields,
+        [("GuessAttempted", "timestamp"), ("end of stream", "AttemptCompleted")]
+    );
+}
+
+#[test]
+fn refund_decisions_are_checked() {
+    let mut stream = honest_session();
+    if let SoddleEvent::GameSessionEnded(ended) = &mut stream.events[4] {
+        ended.deposit_refunded = false;
+        ended.refund_amount = 0;
+    }
+
+    let report = replay(&stream.session, &stream.events);
+    let fields: Vec<_> = report.divergences.iter().map(|d| d.field).collect();
+    assert_eq!(fields, ["deposit_refunded", "refund_amount"]);
+}
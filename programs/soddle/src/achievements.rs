@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::states::game_session::GameSession;
+use crate::states::player_profile::PlayerProfile;
+use crate::states::scoring_rule::ScoringRule;
+
+// Achievements are bits of `PlayerProfile::achievements`, numbered by the enum's
+// discriminant. New ones go at the end so recorded bits keep their meaning.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Achievement {
+    OneGuessSolve,  // Solved an attempt with its first guess
+    SevenDayStreak,
+    PerfectScore,  // Solved an attempt without losing a point
+    AllGameTypes,  // Played every game type at least once
+    FirstSolve,
+    AllGameTypesSolved,  // Solved every game type at least once
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 6] = [
+        Achievement::OneGuessSolve,
+        Achievement::SevenDayStreak,
+        Achievement::PerfectScore,
+        Achievement::AllGameTypes,
+        Achievement::FirstSolve,
+        Achievement::AllGameTypesSolved,
+    ];
+
+    pub fn bit(self) -> u64 {
+        1 << self as u8
+    }
+
+    // Seed of the achievement's badge mint
+    pub fn seed(self) -> [u8; 1] {
+        [self as u8]
+    }
+}
+
+pub const STREAK_ACHIEVEMENT_DAYS: u32 = 7;
+
+/// Bitmask of the game types 1..=GAME_TYPE_COUNT.
+pub fn all_game_types_mask() -> u8 {
+    (1u8 << GAME_TYPE_COUNT) - 1
+}
+
+/// Every achievement the profile qualifies for with `game_session` as its latest session.
+/// Expects the profile's streak and played and solved game types to already include the session.
+/// `rule` is the scoring rule of the session's competition.
+pub fn earned(profile: &PlayerProfile, game_session: &GameSession, rule: &ScoringRule) -> u64 {
+    let solved = game_session.played_attempts().iter().filter(|attempt| attempt.is_solved());
+
+    let mut earned = 0;
+    for attempt in solved {
+        earned |= Achievement::FirstSolve.bit();
+        if attempt.guesses == 1 {
+            earned |= Achievement::OneGuessSolve.bit();
+        }
+        if attempt.is_perfect(rule) {
+            earned |= Achievement::PerfectScore.bit();
+        }
+    }
+    if profile.current_streak >= STREAK_ACHIEVEMENT_DAYS {
+        earned |= Achievement::SevenDayStreak.bit();
+    }
+    if profile.game_types_played & all_game_types_mask() == all_game_types_mask() {
+        earned |= Achievement::AllGameTypes.bit();
+    }
+    if profile.game_types_solved & all_game_types_mask() == all_game_types_mask() {
+        earned |= Achievement::AllGameTypesSolved.bit();
+    }
+    earned
+}
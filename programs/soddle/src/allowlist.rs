@@ -0,0 +1,56 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+// Merkle allowlists of player keys, shared with the off-chain tools that build them.
+// Leaves and inner nodes hash under different prefixes so one can't pass for the
+// other, and each pair is sorted before hashing, so a proof is just the siblings on
+// the way up from the player's leaf.
+
+pub fn leaf(player: &Pubkey) -> [u8; 32] {
+    hashv(&[&[0], player.as_ref()]).to_bytes()
+}
+
+fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[&[1], left, right]).to_bytes()
+}
+
+/// Whether `proof` leads from `player`'s leaf to `root`.
+pub fn verify(root: &[u8; 32], player: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    proof.iter().fold(leaf(player), |hash, sibling| node(&hash, sibling)) == *root
+}
+
+/// The layer above `layer`. An odd node out is carried up as is.
+fn parent_layer(layer: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    layer
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => node(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// The root of the allowlist of `players`, None when there are none.
+pub fn root(players: &[Pubkey]) -> Option<[u8; 32]> {
+    let mut layer: Vec<[u8; 32]> = players.iter().map(leaf).collect();
+    while layer.len() > 1 {
+        layer = parent_layer(&layer);
+    }
+    layer.first().copied()
+}
+
+/// The proof that `players[index]` is on the allowlist of `players`.
+pub fn proof(players: &[Pubkey], mut index: usize) -> Vec<[u8; 32]> {
+    let mut layer: Vec<[u8; 32]> = players.iter().map(leaf).collect();
+    let mut proof = Vec::new();
+    while layer.len() > 1 {
+        if let Some(sibling) = layer.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        layer = parent_layer(&layer);
+        index /= 2;
+    }
+    proof
+}
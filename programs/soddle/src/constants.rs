@@ -1,10 +1,18 @@
-use anchor_lang::prelude::*;
-
 pub const GAME_FEE: u64 = 20_000_000;  // 0.02 SOL in lamports
 pub const MAX_DAILY_ATTEMPTS: u8 = 3;
+pub const MAX_SESSION_ATTEMPTS: u8 = 6; // Attempt slots of a session, for players allowed more than MAX_DAILY_ATTEMPTS
 pub const MAX_GUESSES: u32 = 20;
 pub const ATTEMPT_TIME_LIMIT: i64 = 5 * 60; // 5 minutes in seconds
+pub const SESSION_DURATION: i64 = 24 * 60 * 60; // Sessions expire a day after they start
 pub const INITIAL_SCORE: u32 = 1000;
 pub const TIME_PENALTY_INTERVAL: i64 = 5; // 5 seconds
 pub const WRONG_GUESS_PENALTY: u32 = 50;
-pub const TIME_PENALTY: u32 = 5; // Points deducted every 5 seconds
\ No newline at end of file
+pub const TIME_PENALTY: u32 = 5; // Points deducted every 5 seconds
+pub const GAME_TYPE_COUNT: u8 = 3; // Game types are numbered 1..=GAME_TYPE_COUNT
+pub const EVENT_SCHEMA_VERSION: u8 = 6; // Bump whenever an event's layout changes
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60; // Length of a streak day, days start at 00:00 UTC
+pub const BPS_DENOMINATOR: u32 = 10_000;
+pub const MAX_STREAK_BONUS_BPS: u16 = 10_000; // A streak can at most double a score
+pub const VOUCHER_DOMAIN: &[u8] = b"soddle-voucher";
+pub const VOUCHERS_PER_BITMAP: u64 = 1024; // Nonces tracked by one `VoucherBitmap` page
+pub const MAX_REFERRAL_DEPTH: usize = 16; // Referrers above a new one that registration walks looking for the player
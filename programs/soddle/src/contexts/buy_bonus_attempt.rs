@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::constants::MAX_SESSION_ATTEMPTS;
+use crate::errors::SoddleError;
+use crate::fees;
+use crate::scoring;
+use crate::states::{fee_vault::FeeVault, game_config::GameConfig, game_metrics::GameMetrics, game_session::GameSession};
+use crate::states::player_profile::PlayerProfile;
+use crate::states::fee_ledger::{FeeLedger, FeeParty};
+use crate::states::season_pass::SeasonPass;
+
+#[derive(Accounts)]
+pub struct BuyBonusAttempt<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"game_session",
+            player.key().as_ref(),
+            game_session.competition_id.to_le_bytes().as_ref(),
+            game_session.start_time.to_le_bytes().as_ref()
+        ],
+        bump = game_session.bump,
+        constraint = !game_session.is_expired(Clock::get()?.unix_timestamp) @ SoddleError::SessionExpired,
+        constraint = !game_session.is_practice @ SoddleError::PracticeSession
+    )]
+    pub game_session: Account<'info, GameSession>,
+
+    // The pass's attempts come before any bought ones
+    #[account(
+        seeds = [b"season_pass", player.key().as_ref()],
+        bump = season_pass.bump
+    )]
+    pub season_pass: Option<Account<'info, SeasonPass>>,
+
+    // Counts the player's purchases of the day, over all their sessions
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        seeds = [b"game_config"],
+        bump = config.bump,
+        constraint = config.bonus_attempt_price > 0 @ SoddleError::BonusAttemptsUnavailable
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game_metrics"],
+        bump = metrics.bump
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump,
+        constraint = fee_vault.key() == metrics.fee_vault @ SoddleError::InvalidFeeVault
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    // Bonus attempts are paid in lamports and belong to the treasury alone
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"treasury_ledger", fee_vault.key().as_ref(), Pubkey::default().as_ref()],
+        bump
+    )]
+    pub treasury_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn buy_bonus_attempt(ctx: Context<BuyBonusAttempt>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let game_session = &mut ctx.accounts.game_session;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let free_attempts = SeasonPass::free_attempts(ctx.accounts.season_pass.as_deref(), current_time);
+    require!(
+        game_session.attempt_limit(free_attempts) < MAX_SESSION_ATTEMPTS,
+        SoddleError::BonusAttemptLimitReached
+    );
+
+    let profile = &mut ctx.accounts.player_profile;
+    if profile.player == Pubkey::default() {
+        profile.player = ctx.accounts.player.key();
+        profile.bump = ctx.bumps.player_profile;
+    }
+
+    // Each attempt the player buys in a day costs more than the one before, whichever
+    // session it is for, so opening a new session doesn't reset the price
+    let day = scoring::day_index(current_time);
+    let price = fees::bonus_attempt_price(
+        config.bonus_attempt_price,
+        config.bonus_attempt_price_step_bps,
+        profile.bonus_attempts_bought_on(day),
+    )
+    .ok_or(SoddleError::NumericOverflow)?;
+    profile.record_bonus_attempt(day)?;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.player.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, price)?;
+    ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault.total_collected
+        .checked_add(price)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    let metrics = &mut ctx.accounts.metrics;
+    metrics.total_fees_collected = metrics.total_fees_collected
+        .checked_add(price)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    // Not refundable, so credited right away rather than held with the session's deposit
+    let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+    treasury_ledger.open(FeeParty::Treasury, ctx.accounts.fee_vault.key(), None, ctx.bumps.treasury_ledger);
+    treasury_ledger.credit(price)?;
+
+    game_session.bonus_attempts = game_session.bonus_attempts
+        .checked_add(1)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::SoddleError;
+use crate::states::{fee_vault::FeeVault, game_config::GameConfig, game_metrics::GameMetrics, season_pass::SeasonPass};
+use crate::states::fee_ledger::{FeeLedger, FeeParty};
+
+#[derive(Accounts)]
+pub struct BuySeasonPass<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + SeasonPass::INIT_SPACE,
+        seeds = [b"season_pass", player.key().as_ref()],
+        bump
+    )]
+    pub season_pass: Account<'info, SeasonPass>,
+
+    #[account(
+        seeds = [b"game_config"],
+        bump = config.bump,
+        constraint = config.season_pass_price > 0 @ SoddleError::SeasonPassUnavailable
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game_metrics"],
+        bump = metrics.bump
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump,
+        constraint = fee_vault.key() == metrics.fee_vault @ SoddleError::InvalidFeeVault
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    // Passes are paid in lamports and belong to the treasury alone
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"treasury_ledger", fee_vault.key().as_ref(), Pubkey::default().as_ref()],
+        bump
+    )]
+    pub treasury_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn buy_season_pass(ctx: Context<BuySeasonPass>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let price = config.season_pass_price;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.player.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, price)?;
+    ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault.total_collected
+        .checked_add(price)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    // Kept apart from the per-attempt fees
+    let metrics = &mut ctx.accounts.metrics;
+    metrics.total_season_pass_revenue = metrics.total_season_pass_revenue
+        .checked_add(price)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+    treasury_ledger.open(FeeParty::Treasury, ctx.accounts.fee_vault.key(), None, ctx.bumps.treasury_ledger);
+    treasury_ledger.credit(price)?;
+
+    let season_pass = &mut ctx.accounts.season_pass;
+    season_pass.player = ctx.accounts.player.key();
+    season_pass.bonus_attempts = config.season_pass_bonus_attempts;
+    season_pass.extend(current_time, config.season_pass_duration)
+        .ok_or(SoddleError::NumericOverflow)?;
+    season_pass.total_paid = season_pass.total_paid
+        .checked_add(price)
+        .ok_or(SoddleError::NumericOverflow)?;
+    season_pass.bump = ctx.bumps.season_pass;
+
+    Ok(())
+}
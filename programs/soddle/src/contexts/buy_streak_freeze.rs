@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::SoddleError;
+use crate::states::{fee_vault::FeeVault, game_config::GameConfig, game_metrics::GameMetrics, player_profile::PlayerProfile};
+use crate::states::fee_ledger::{FeeLedger, FeeParty};
+
+#[derive(Accounts)]
+pub struct BuyStreakFreeze<'info> {
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+        has_one = player @ SoddleError::InvalidPlayer,
+        constraint = player_profile.streak_freezes < config.max_streak_freezes @ SoddleError::StreakFreezeLimitReached
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        seeds = [b"game_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"game_metrics"],
+        bump = metrics.bump
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump,
+        constraint = fee_vault.key() == metrics.fee_vault @ SoddleError::InvalidFeeVault
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    // Freezes are paid in lamports and belong to the treasury alone
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"treasury_ledger", fee_vault.key().as_ref(), Pubkey::default().as_ref()],
+        bump
+    )]
+    pub treasury_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn buy_streak_freeze(ctx: Context<BuyStreakFreeze>) -> Result<()> {
+    let price = ctx.accounts.config.streak_freeze_price;
+
+    // Paid into the fee vault the same way as the game fee
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.player.to_account_info(),
+            to: ctx.accounts.fee_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, price)?;
+    ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault.total_collected
+        .checked_add(price)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    let metrics = &mut ctx.accounts.metrics;
+    metrics.total_fees_collected = metrics.total_fees_collected
+        .checked_add(price)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+    treasury_ledger.open(FeeParty::Treasury, ctx.accounts.fee_vault.key(), None, ctx.bumps.treasury_ledger);
+    treasury_ledger.credit(price)?;
+
+    let profile = &mut ctx.accounts.player_profile;
+    profile.streak_freezes = profile.streak_freezes
+        .checked_add(1)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::{self, MintTo, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::achievements::Achievement;
+use crate::errors::SoddleError;
+use crate::states::player_profile::PlayerProfile;
+
+#[derive(Accounts)]
+#[instruction(achievement: Achievement)]
+pub struct ClaimBadge<'info> {
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+        has_one = player @ SoddleError::InvalidPlayer,
+        constraint = player_profile.achievements & achievement.bit() != 0 @ SoddleError::BadgeNotEarned,
+        constraint = player_profile.badges_claimed & achievement.bit() == 0 @ SoddleError::BadgeAlreadyClaimed
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"badge_mint", achievement.seed().as_ref()],
+        bump,
+        mint::token_program = token_program
+    )]
+    pub badge_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = badge_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub badge_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_badge(ctx: Context<ClaimBadge>, achievement: Achievement) -> Result<()> {
+    let seed = achievement.seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"badge_mint", seed.as_ref(), &[ctx.bumps.badge_mint]]];
+
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.badge_mint.to_account_info(),
+                to: ctx.accounts.badge_account.to_account_info(),
+                authority: ctx.accounts.badge_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    let profile = &mut ctx.accounts.player_profile;
+    profile.badges_claimed |= achievement.bit();
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::contexts::token_fee;
+use crate::errors::SoddleError;
+use crate::states::{fee_ledger::FeeLedger, fee_vault::FeeVault, kol::Kol};
+
+#[derive(Accounts)]
+pub struct ClaimKolRewards<'info> {
+    #[account(
+        seeds = [
+            b"kol",
+            kol.id.to_le_bytes().as_ref()
+        ],
+        bump = kol.bump,
+        constraint = kol.payout_wallet == Some(payout_wallet.key()) @ SoddleError::InvalidPayoutWallet
+    )]
+    pub kol: Account<'info, Kol>,
+
+    // The KOL's ledger in the currency being claimed
+    #[account(
+        mut,
+        seeds = [
+            b"kol_ledger",
+            kol.key().as_ref(),
+            fee_mint.as_ref().map(|mint| mint.key()).unwrap_or_default().as_ref()
+        ],
+        bump = kol_ledger.bump
+    )]
+    pub kol_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    pub payout_wallet: Signer<'info>,
+
+    // Given together to claim a token ledger instead of the lamport one
+    #[account(mint::token_program = token_program)]
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", token_vault.mint.as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = payout_token_account.owner == payout_wallet.key() @ SoddleError::InvalidPayoutWallet
+    )]
+    pub payout_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn claim_kol_rewards(ctx: Context<ClaimKolRewards>) -> Result<()> {
+    let amount = ctx.accounts.kol_ledger.balance;
+    require!(amount > 0, SoddleError::NoRewardsToClaim);
+    ctx.accounts.kol_ledger.pay_out(amount)?;
+
+    match &ctx.accounts.fee_mint {
+        None => {
+            // Same direct move as `withdraw_fees`, the vault being a program-owned account
+            ctx.accounts.fee_vault.sub_lamports(amount)?;
+            ctx.accounts.payout_wallet.add_lamports(amount)?;
+            ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault.total_collected
+                .checked_sub(amount)
+                .ok_or(SoddleError::NumericOverflow)?;
+        }
+        Some(mint) => {
+            let (Some(vault), Some(to), Some(token_program)) = (
+                &ctx.accounts.token_vault,
+                &ctx.accounts.payout_token_account,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(SoddleError::MissingTokenAccounts);
+            };
+            require!(vault.mint == mint.key(), SoddleError::InvalidFeeMint);
+
+            token_fee::pay_out(token_program, mint, vault, to, &ctx.accounts.fee_vault, amount)?;
+        }
+    }
+
+    Ok(())
+}
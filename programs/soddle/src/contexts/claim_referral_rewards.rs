@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::contexts::token_fee;
+use crate::errors::SoddleError;
+use crate::states::{fee_ledger::FeeLedger, fee_vault::FeeVault};
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    // The referrer's ledger in the currency being claimed
+    #[account(
+        mut,
+        seeds = [
+            b"referrer_ledger",
+            referrer.key().as_ref(),
+            fee_mint.as_ref().map(|mint| mint.key()).unwrap_or_default().as_ref()
+        ],
+        bump = referrer_ledger.bump
+    )]
+    pub referrer_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    // Given together to claim a token ledger instead of the lamport one
+    #[account(mint::token_program = token_program)]
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", token_vault.mint.as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = referrer_token_account.owner == referrer.key() @ SoddleError::InvalidPayoutWallet
+    )]
+    pub referrer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let amount = ctx.accounts.referrer_ledger.balance;
+    require!(amount > 0, SoddleError::NoRewardsToClaim);
+    ctx.accounts.referrer_ledger.pay_out(amount)?;
+
+    match &ctx.accounts.fee_mint {
+        None => {
+            ctx.accounts.fee_vault.sub_lamports(amount)?;
+            ctx.accounts.referrer.add_lamports(amount)?;
+            ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault.total_collected
+                .checked_sub(amount)
+                .ok_or(SoddleError::NumericOverflow)?;
+        }
+        Some(mint) => {
+            let (Some(vault), Some(to), Some(token_program)) = (
+                &ctx.accounts.token_vault,
+                &ctx.accounts.referrer_token_account,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(SoddleError::MissingTokenAccounts);
+            };
+            require!(vault.mint == mint.key(), SoddleError::InvalidFeeMint);
+
+            token_fee::pay_out(token_program, mint, vault, to, &ctx.accounts.fee_vault, amount)?;
+        }
+    }
+
+    Ok(())
+}
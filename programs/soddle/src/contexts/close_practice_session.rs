@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::errors::SoddleError;
+use crate::states::game_session::GameSession;
+
+// Practice sessions hold no deposit and count for nothing, so they can be closed
+// at any point, even mid-attempt or once expired
+#[derive(Accounts)]
+pub struct ClosePracticeSession<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"game_session",
+            player.key().as_ref(),
+            game_session.competition_id.to_le_bytes().as_ref(),
+            game_session.start_time.to_le_bytes().as_ref()
+        ],
+        bump = game_session.bump,
+        constraint = game_session.is_practice @ SoddleError::NotPracticeSession,
+        close = player
+    )]
+    pub game_session: Account<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+pub fn close_practice_session(_ctx: Context<ClosePracticeSession>) -> Result<()> {
+    Ok(())
+}
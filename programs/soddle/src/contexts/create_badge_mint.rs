@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use anchor_spl::token_2022::{self, InitializeMint2, Token2022};
+use anchor_spl::token_2022_extensions::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, token_metadata_initialize,
+    MetadataPointerInitialize, NonTransferableMintInitialize, TokenMetadataInitialize,
+};
+use crate::achievements::Achievement;
+use crate::errors::SoddleError;
+use crate::states::game_metrics::GameMetrics;
+
+#[derive(Accounts)]
+#[instruction(achievement: Achievement)]
+pub struct CreateBadgeMint<'info> {
+    /// CHECK: Created below as a Token-2022 mint; the address is pinned by the seeds
+    #[account(
+        mut,
+        seeds = [b"badge_mint", achievement.seed().as_ref()],
+        bump
+    )]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"game_metrics"],
+        bump = metrics.bump,
+        constraint = metrics.authority == authority.key() @ SoddleError::UnauthorizedAuthority
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+// Space the token metadata extension takes once initialized: TLV header, update
+// authority, mint, the three strings and an empty additional-metadata list
+fn metadata_space(name: &str, symbol: &str, uri: &str) -> usize {
+    4 + 32 + 32 + (4 + name.len()) + (4 + symbol.len()) + (4 + uri.len()) + 4
+}
+
+pub fn create_badge_mint(
+    ctx: Context<CreateBadgeMint>,
+    achievement: Achievement,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let mint = ctx.accounts.badge_mint.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let seed = achievement.seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[b"badge_mint", seed.as_ref(), &[ctx.bumps.badge_mint]]];
+
+    // The mint is its own mint authority, so only this program can issue badges.
+    // Metadata is appended after initialization, the lamports for it are paid upfront.
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::MetadataPointer,
+    ])?;
+    let lamports = Rent::get()?.minimum_balance(space + metadata_space(&name, &symbol, &uri));
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: ctx.accounts.authority.to_account_info(),
+                to: mint.clone(),
+            },
+            signer_seeds,
+        ),
+        lamports,
+        space as u64,
+        &token_program.key(),
+    )?;
+
+    non_transferable_mint_initialize(CpiContext::new(
+        token_program.clone(),
+        NonTransferableMintInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        },
+    ))?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(ctx.accounts.metrics.authority),
+        Some(mint.key()),
+    )?;
+    token_2022::initialize_mint2(
+        CpiContext::new(token_program.clone(), InitializeMint2 { mint: mint.clone() }),
+        0,
+        &mint.key(),
+        None,
+    )?;
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+                metadata: mint.clone(),
+                mint_authority: mint.clone(),
+                update_authority: ctx.accounts.authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        name,
+        symbol,
+        uri,
+    )?;
+
+    Ok(())
+}
@@ -1,6 +1,19 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use crate::achievements::{self, Achievement};
+use crate::constants::EVENT_SCHEMA_VERSION;
+use crate::errors::SoddleError;
+use crate::events::{AchievementUnlocked, GameSessionEnded, NewHighScore, StreakUpdated};
+use crate::states::{fee_vault::FeeVault, game_metrics::GameMetrics, game_session::GameSession};
+use crate::scoring;
+use crate::states::game_config::GameConfig;
+use crate::states::player_profile::PlayerProfile;
+use crate::states::token_metrics::TokenMetrics;
+use crate::states::competition::Competition;
+use crate::states::fee_ledger::{FeeLedger, FeeParty};
+use crate::contexts::token_fee;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct EndGameSession<'info> {
     #[account(
@@ -12,8 +25,9 @@ pub struct EndGameSession<'info> {
             game_session.start_time.to_le_bytes().as_ref()
         ],
         bump = game_session.bump,
-        constraint = !game_session.is_expired() @ GameError::SessionExpired,
-        constraint = game_session.player == player.key() @ GameError::InvalidPlayer,
+        constraint = !game_session.is_expired(Clock::get()?.unix_timestamp) @ SoddleError::SessionExpired,
+        constraint = game_session.player == player.key() @ SoddleError::InvalidPlayer,
+        constraint = !game_session.is_practice @ SoddleError::PracticeSession,
         close = player
     )]
     pub game_session: Account<'info, GameSession>,
@@ -32,9 +46,106 @@ pub struct EndGameSession<'info> {
     )]
     pub fee_vault: Account<'info, FeeVault>,
 
+    #[account(
+        seeds = [b"game_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        seeds = [
+            b"competition",
+            competition.game_state.as_ref(),
+            game_session.competition_id.to_le_bytes().as_ref()
+        ],
+        bump = competition.bump,
+        address = game_session.competition @ SoddleError::InvalidCompetition
+    )]
+    pub competition: Account<'info, Competition>,
+
+    // Ledgers holding the session's pending fee shares. Created here as well, for
+    // sessions forfeited before any fee was paid.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"treasury_ledger", fee_vault.key().as_ref(), game_session.fee_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub treasury_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"prize_pool_ledger", competition.key().as_ref(), game_session.fee_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub prize_pool_ledger: Box<Account<'info, FeeLedger>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"kol_ledger", game_session.kol.as_ref(), game_session.fee_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub kol_ledger: Box<Account<'info, FeeLedger>>,
+
+    // Needed when the session was charged with a referrer
+    #[account(
+        mut,
+        seeds = [
+            b"referrer_ledger",
+            game_session.referrer.unwrap_or_default().as_ref(),
+            game_session.fee_mint.unwrap_or_default().as_ref()
+        ],
+        bump = referrer_ledger.bump
+    )]
+    pub referrer_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    // Created with the player's first finished session, unless a bought attempt came first
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
     #[account(mut)]
     pub player: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    // Only for sessions whose deposit was paid in an SPL token
+    #[account(
+        address = game_session.fee_mint.unwrap_or_default() @ SoddleError::InvalidFeeMint,
+        mint::token_program = token_program
+    )]
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", game_session.fee_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metrics", game_session.fee_mint.unwrap_or_default().as_ref()],
+        bump = token_metrics.bump
+    )]
+    pub token_metrics: Option<Account<'info, TokenMetrics>>,
+
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key() @ SoddleError::InvalidPlayer
+    )]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 
@@ -46,75 +157,168 @@ pub struct EndGameSession<'info> {
         // 1. Validate game can be ended
         require!(
             game_session.can_end_session(),
-            GameError::CannotEndGameYet
+            SoddleError::GameSessionNotEnded
         );
 
-        // 2. Update metrics
+        // 2. Update the streak, which sets the session's final score. Forfeited sessions
+        // have no day to count.
+        let profile = &mut ctx.accounts.player_profile;
+        if profile.player == Pubkey::default() {
+            profile.player = game_session.player;
+            profile.bump = ctx.bumps.player_profile;
+        }
+        let config = &ctx.accounts.config;
+        let mut bonus_bps = 0;
+        if game_session.current_attempt > 0 {
+            let day = scoring::day_index(game_session.attempts[0].timestamp);
+            profile.record_streak(day, game_session.is_solved());
+
+            bonus_bps = scoring::streak_bonus_bps(
+                profile.streak_on(day),
+                config.streak_bonus_bps,
+                config.max_streak_bonus_bps,
+            );
+
+            emit_cpi!(StreakUpdated {
+                version: EVENT_SCHEMA_VERSION,
+                session: game_session.key(),
+                competition_id: game_session.competition_id,
+                game_type: game_session.game_type,
+                player: game_session.player,
+                day,
+                current_streak: profile.current_streak,
+                longest_streak: profile.longest_streak,
+                bonus_bps,
+                timestamp: current_time,
+            });
+        }
+
+        let best_score = game_session.best_score();
+        let total_score = game_session.final_score(bonus_bps);
+
+        // 3. Update metrics
         metrics.total_games_completed = metrics.total_games_completed
             .checked_add(1)
-            .ok_or(GameError::NumericOverflow)?;
-
-        // Calculate best score from attempts
-        let best_score = game_session.attempts
-            .iter()
-            .map(|attempt| attempt.score)
-            .max()
-            .unwrap_or(0);
-
-        // Update high score metrics if applicable
-        if best_score > metrics.highest_score {
-            metrics.highest_score = best_score;
+            .ok_or(SoddleError::NumericOverflow)?;
+
+        let is_new_high_score = total_score > metrics.highest_score;
+        if is_new_high_score {
+            metrics.highest_score = total_score;
             metrics.highest_scorer = game_session.player;
         }
 
-        // 3. Handle deposit refund if conditions met
-        if game_session.should_refund_deposit() && game_session.deposit > 0 {
-            let signer_seeds = &[
-                b"fee_vault".as_ref(),
-                &[ctx.accounts.fee_vault.bump],
-            ];
-
-            // Transfer deposit back to player
-            let transfer_ix = system_program::Transfer {
-                from: ctx.accounts.fee_vault.to_account_info(),
-                to: ctx.accounts.player.to_account_info(),
-            };
+        // 4. Handle deposit refund if conditions met
+        let refunded = game_session.should_refund_deposit();
+        if refunded && game_session.deposit > 0 {
+            match game_session.fee_mint {
+                None => {
+                    // The vault is a program-owned data account, which the system program
+                    // refuses to debit, so the deposit is moved back directly
+                    ctx.accounts.fee_vault.sub_lamports(game_session.deposit)?;
+                    ctx.accounts.player.add_lamports(game_session.deposit)?;
 
-            let cpi_ctx = CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                transfer_ix,
-                &[&signer_seeds[..]],
-            );
+                    // Update fee vault total
+                    ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault.total_collected
+                        .checked_sub(game_session.deposit)
+                        .ok_or(SoddleError::NumericOverflow)?;
+                }
+                Some(_) => {
+                    let (Some(mint), Some(vault), Some(token_metrics), Some(to), Some(token_program)) = (
+                        &ctx.accounts.fee_mint,
+                        &ctx.accounts.token_vault,
+                        &mut ctx.accounts.token_metrics,
+                        &ctx.accounts.player_token_account,
+                        &ctx.accounts.token_program,
+                    ) else {
+                        return err!(SoddleError::MissingTokenAccounts);
+                    };
 
-            system_program::transfer(cpi_ctx, game_session.deposit)?;
+                    token_fee::pay_out(token_program, mint, vault, to, &ctx.accounts.fee_vault, game_session.deposit)?;
 
-            // Update fee vault total
-            ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault.total_collected
-                .checked_sub(game_session.deposit)
-                .ok_or(GameError::NumericOverflow)?;
+                    token_metrics.total_refunded = token_metrics.total_refunded
+                        .checked_add(game_session.deposit)
+                        .ok_or(SoddleError::NumericOverflow)?;
+                }
+            }
         }
 
-        // 4. Emit completion events
-        emit!(GameSessionEnded {
-            player: game_session.player,
+        // Release the fee shares to their ledgers, or drop them if the deposit went back
+        let split = game_session.fee_split;
+        let fee_mint = game_session.fee_mint;
+
+        let treasury_ledger = &mut ctx.accounts.treasury_ledger;
+        treasury_ledger.open(FeeParty::Treasury, ctx.accounts.fee_vault.key(), fee_mint, ctx.bumps.treasury_ledger);
+        treasury_ledger.settle(split.treasury, refunded)?;
+
+        let prize_pool_ledger = &mut ctx.accounts.prize_pool_ledger;
+        prize_pool_ledger.open(FeeParty::PrizePool, ctx.accounts.competition.key(), fee_mint, ctx.bumps.prize_pool_ledger);
+        prize_pool_ledger.settle(split.prize_pool, refunded)?;
+
+        let kol_ledger = &mut ctx.accounts.kol_ledger;
+        kol_ledger.open(FeeParty::Kol, game_session.kol, fee_mint, ctx.bumps.kol_ledger);
+        kol_ledger.settle(split.kol, refunded)?;
+
+        if game_session.referrer.is_some() {
+            let Some(referrer_ledger) = ctx.accounts.referrer_ledger.as_mut() else {
+                return err!(SoddleError::MissingReferrerLedger);
+            };
+            referrer_ledger.settle(split.referrer, refunded)?;
+        }
+
+        // 5. Update the player's lifetime stats
+        let refund_amount = if refunded {
+            game_session.deposit
+        } else {
+            0
+        };
+        profile.record_session(game_session, total_score, refund_amount)?;
+
+        // 6. Unlock achievements
+        if game_session.current_attempt > 0 {
+            profile.game_types_played |= 1 << (game_session.game_type - 1);
+        }
+        if game_session.is_solved() {
+            profile.game_types_solved |= 1 << (game_session.game_type - 1);
+        }
+        let unlocked = achievements::earned(profile, game_session, &ctx.accounts.competition.scoring_rule) & !profile.achievements;
+        profile.achievements |= unlocked;
+        for achievement in Achievement::ALL {
+            if unlocked & achievement.bit() != 0 {
+                emit_cpi!(AchievementUnlocked {
+                    version: EVENT_SCHEMA_VERSION,
+                    session: game_session.key(),
+                    competition_id: game_session.competition_id,
+                    game_type: game_session.game_type,
+                    player: game_session.player,
+                    achievement,
+                    timestamp: current_time,
+                });
+            }
+        }
+
+        // 7. Emit completion events
+        emit_cpi!(GameSessionEnded {
+            version: EVENT_SCHEMA_VERSION,
+            session: game_session.key(),
             competition_id: game_session.competition_id,
+            game_type: game_session.game_type,
+            player: game_session.player,
             total_attempts: game_session.current_attempt,
             best_score,
-            total_score: game_session.total_score,
-            deposit_refunded: game_session.should_refund_deposit(),
-            refund_amount: if game_session.should_refund_deposit() { 
-                game_session.deposit 
-            } else { 
-                0 
-            },
+            total_score,
+            deposit_refunded: refunded,
+            refund_amount,
             timestamp: current_time,
         });
 
-        if best_score > metrics.highest_score {
-            emit!(NewHighScore {
-                player: game_session.player,
-                score: best_score,
+        if is_new_high_score {
+            emit_cpi!(NewHighScore {
+                version: EVENT_SCHEMA_VERSION,
+                session: game_session.key(),
                 competition_id: game_session.competition_id,
+                game_type: game_session.game_type,
+                player: game_session.player,
+                score: total_score,
                 timestamp: current_time,
             });
         }
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::errors::SoddleError;
+use crate::states::{competition::Competition, fee_vault::FeeVault, game_session::GameSession};
+use crate::states::fee_ledger::FeeLedger;
+
+// Settles a session that expired before it was ended. The deposit is forfeited, so its
+// pending fee shares are earned by their ledgers; anyone may call this, and the session's
+// rent goes back to its player.
+#[derive(Accounts)]
+pub struct ExpireGameSession<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"game_session",
+            player.key().as_ref(),
+            game_session.competition_id.to_le_bytes().as_ref(),
+            game_session.start_time.to_le_bytes().as_ref()
+        ],
+        bump = game_session.bump,
+        constraint = game_session.is_expired(Clock::get()?.unix_timestamp) @ SoddleError::SessionNotExpired,
+        constraint = !game_session.is_practice @ SoddleError::PracticeSession,
+        close = player
+    )]
+    pub game_session: Account<'info, GameSession>,
+
+    #[account(
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(address = game_session.competition @ SoddleError::InvalidCompetition)]
+    pub competition: Account<'info, Competition>,
+
+    // Needed for the shares the session holds; a session that never started an attempt has none
+    #[account(
+        mut,
+        seeds = [b"treasury_ledger", fee_vault.key().as_ref(), game_session.fee_mint.unwrap_or_default().as_ref()],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    #[account(
+        mut,
+        seeds = [b"prize_pool_ledger", competition.key().as_ref(), game_session.fee_mint.unwrap_or_default().as_ref()],
+        bump = prize_pool_ledger.bump
+    )]
+    pub prize_pool_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    #[account(
+        mut,
+        seeds = [b"kol_ledger", game_session.kol.as_ref(), game_session.fee_mint.unwrap_or_default().as_ref()],
+        bump = kol_ledger.bump
+    )]
+    pub kol_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"referrer_ledger",
+            game_session.referrer.unwrap_or_default().as_ref(),
+            game_session.fee_mint.unwrap_or_default().as_ref()
+        ],
+        bump = referrer_ledger.bump
+    )]
+    pub referrer_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    /// CHECK: Only receives the session's rent; the session seeds tie it to the player
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+}
+
+// Earns a pending share; the ledger may only be left out when there is nothing to release
+fn release(ledger: Option<&mut Box<Account<FeeLedger>>>, amount: u64, missing: SoddleError) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    match ledger {
+        Some(ledger) => ledger.settle(amount, false),
+        None => Err(missing.into()),
+    }
+}
+
+pub fn expire_game_session(ctx: Context<ExpireGameSession>) -> Result<()> {
+    let split = ctx.accounts.game_session.fee_split;
+
+    release(ctx.accounts.treasury_ledger.as_mut(), split.treasury, SoddleError::MissingFeeLedgers)?;
+    release(ctx.accounts.prize_pool_ledger.as_mut(), split.prize_pool, SoddleError::MissingFeeLedgers)?;
+    release(ctx.accounts.kol_ledger.as_mut(), split.kol, SoddleError::MissingFeeLedgers)?;
+    release(ctx.accounts.referrer_ledger.as_mut(), split.referrer, SoddleError::MissingReferrerLedger)?;
+
+    Ok(())
+}
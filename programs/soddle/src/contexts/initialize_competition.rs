@@ -1,37 +1,64 @@
 use anchor_lang::prelude::*;
 use crate::states::game_state::GameState;
 use crate::errors::SoddleError;
+use crate::states::competition::Competition;
+use crate::states::score_model::ScoreModel;
+use crate::states::scoring_rule::ScoringRule;
+use crate::states::token_gate::TokenGate;
 
 #[derive(Accounts)]
+#[instruction(id: u64)]
 pub struct InitializeCompetition<'info> {
-    #[account(mut)]
+    // Only the authority whose game state will hold the competition
+    #[account(
+        mut,
+        seeds = [b"game_state", authority.key().as_ref()],
+        bump = game_state.bump
+    )]
     pub game_state: Account<'info, GameState>,
 
     #[account(
         init,
-        payer = payer,
+        payer = authority,
         space = 8 + Competition::INIT_SPACE,
         seeds = [
             b"competition",
             game_state.key().as_ref(),
-            competition.id.to_le_bytes().as_ref()
+            id.to_le_bytes().as_ref()
         ],
         bump
     )]
     pub competition: Account<'info, Competition>,
 
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_competition(
     ctx: Context<InitializeCompetition>,
     id: u64,
     start_time: i64,
     end_time: i64,
+    scoring_rule: ScoringRule,
+    score_model: ScoreModel,
+    fee_mint: Option<Pubkey>,
+    fee_amount: u64,
+    token_gate: Option<TokenGate>,
 ) -> Result<()> {
     require!(start_time < end_time, SoddleError::InvalidTimeRange);
+    require!(scoring_rule.is_valid(), SoddleError::InvalidScoringRule);
+    require!(score_model.is_valid(), SoddleError::InvalidScoreModel);
+    // Lamport competitions always charge GAME_FEE
+    require!(
+        fee_mint.is_some() == (fee_amount > 0),
+        SoddleError::InvalidFeeAmount
+    );
+    require!(
+        token_gate.iter().all(|gate| gate.is_valid()),
+        SoddleError::InvalidTokenGate
+    );
     require!(
         Clock::get()?.unix_timestamp <= start_time,
         SoddleError::CompetitionStarted
@@ -42,6 +69,12 @@ pub fn initialize_competition(
     competition.start_time = start_time;
     competition.end_time = end_time;
     competition.game_state = ctx.accounts.game_state.key();
+    competition.scoring_rule = scoring_rule;
+    competition.score_model = score_model;
+    competition.fee_mint = fee_mint;
+    competition.fee_amount = fee_amount;
+    competition.token_gate = token_gate;
+    competition.allowlist_root = None;  // Set with set_allowlist_root before the start
     competition.bump = ctx.bumps.competition;
 
     Ok(())
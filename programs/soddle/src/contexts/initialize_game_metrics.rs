@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::states::game_metrics::GameMetrics;
+use crate::states::fee_vault::FeeVault;
+use crate::states::game_config::GameConfig;
 
 #[derive(Accounts)]
 pub struct InitializeGameMetrics<'info> {
@@ -21,6 +23,16 @@ pub struct InitializeGameMetrics<'info> {
     )]
     pub fee_vault: Account<'info, FeeVault>,
 
+    // Sessions read it, so it exists before the first one; all zero until the set_* instructions
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [b"game_config"],
+        bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -29,6 +41,7 @@ pub struct InitializeGameMetrics<'info> {
 pub fn initialize_metrics(
     ctx: Context<InitializeGameMetrics>
 ) -> Result<()> {
+    let fee_vault_key = ctx.accounts.fee_vault.key();
     let metrics = &mut ctx.accounts.metrics;
     let fee_vault = &mut ctx.accounts.fee_vault;
 
@@ -36,7 +49,11 @@ pub fn initialize_metrics(
     metrics.total_fees_collected = 0;
     metrics.total_games_started = 0;
     metrics.authority = ctx.accounts.authority.key();
-    metrics.fee_vault = ctx.accounts.fee_vault.key();
+    metrics.fee_vault = fee_vault_key;
+    metrics.total_season_pass_revenue = 0;
+    metrics.total_games_completed = 0;
+    metrics.highest_score = 0;
+    metrics.highest_scorer = Pubkey::default();
     metrics.bump = ctx.bumps.metrics;
 
     // Initialize fee vault account
@@ -44,6 +61,9 @@ pub fn initialize_metrics(
     fee_vault.total_collected = 0;
     fee_vault.bump = ctx.bumps.fee_vault;
 
+    // Settings already made are kept
+    ctx.accounts.config.bump = ctx.bumps.config;
+
     // Log initialization
     msg!("Initialized GameMetrics with authority: {}", metrics.authority);
     msg!("Initialized FeeVault with authority: {}", fee_vault.authority);
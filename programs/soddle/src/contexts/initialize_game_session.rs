@@ -1,13 +1,19 @@
 use anchor_lang::prelude::*;
-use crate::states::{kol::Kol, game_session::GameSession, game_attempt::GameAttempt};
+use crate::states::{kol::Kol, game_session::GameSession};
+use crate::states::competition::Competition;
+use crate::states::fee_split::FeeSplit;
+use crate::constants::GAME_TYPE_COUNT;
+use crate::errors::SoddleError;
+use crate::allowlist;
 
 
 #[derive(Accounts)]
+#[instruction(competition_id: u64, start_time: i64)]
 pub struct InitializeGameSession<'info> {
     #[account(
         init,
         payer = player,
-        space = 8 + GameSession::INIT_SPACE,
+        space = GameSession::INITIAL_SPACE,
         seeds = [
             b"game_session",
             player.key().as_ref(),
@@ -27,6 +33,17 @@ pub struct InitializeGameSession<'info> {
         bump = kol.bump,
     )]
     pub kol: Account<'info, Kol>,
+
+    // Sets the currency every attempt of the session pays in
+    #[account(
+        seeds = [
+            b"competition",
+            competition.game_state.as_ref(),
+            competition_id.to_le_bytes().as_ref()
+        ],
+        bump = competition.bump,
+    )]
+    pub competition: Account<'info, Competition>,
     
     #[account(mut)]
     pub player: Signer<'info>,
@@ -37,28 +54,44 @@ pub fn initialize_game_session(
     ctx: Context<InitializeGameSession>,
     competition_id: u64,
     start_time: i64,
+    game_type: u8,
+    is_practice: bool,
+    proof: Vec<[u8; 32]>,
 ) -> Result<()> {
+    require!(
+        (1..=GAME_TYPE_COUNT).contains(&game_type),
+        SoddleError::InvalidGameType
+    );
+    // Invite-only competitions admit a player once, here, so attempts don't repeat the proof
+    require!(
+        ctx.accounts.competition.allowlist_root.iter().all(|root| {
+            allowlist::verify(root, &ctx.accounts.player.key(), &proof)
+        }),
+        SoddleError::NotOnAllowlist
+    );
     let current_time = Clock::get()?.unix_timestamp;
     require!(
         start_time >= current_time,
-        GameError::InvalidStartTime
+        SoddleError::InvalidStartTime
     );
     let game_session = &mut ctx.accounts.game_session;
     game_session.player = ctx.accounts.player.key();
-    game_session.start_time = current_timestamp;
+    game_session.start_time = start_time;  // Must match the seed so later instructions can re-derive the PDA
     game_session.total_score = 0;
     game_session.deposit = 0;
+    game_session.fee_mint = ctx.accounts.competition.fee_mint;  // Every attempt pays in this currency
+    game_session.fee_split = FeeSplit::default();
+    game_session.referrer = None;
     game_session.kol = ctx.accounts.kol.key();  // Store KOL's PDA address
     game_session.competition_id = competition_id;
+    game_session.competition = ctx.accounts.competition.key();
+    game_session.game_type = game_type;
+    game_session.is_practice = is_practice;
     game_session.current_attempt = 0;
+    game_session.bonus_attempts = 0;
     game_session.bump = ctx.bumps.game_session;
     
-    game_session.attempts = [GameAttempt {
-        score: 0,
-        guesses: 0,
-        completed: false,
-        timestamp: 0,
-    }; 3];
+    game_session.attempts = Default::default();
     
     Ok(())
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::states::{game_session::GameSession, guess_verifier::GuessVerifier};
 
 #[derive(Accounts)]
 pub struct InitializeVerifier<'info> {
@@ -7,7 +8,7 @@ pub struct InitializeVerifier<'info> {
         payer = oracle_authority,
         space = 8 + GuessVerifier::INIT_SPACE,
         seeds = [
-            b"guess_verifier",
+            game_session.verifier_seed(),
             game_session.key().as_ref(),
         ],
         bump
@@ -3,6 +3,7 @@ use crate::states::kol::Kol;
 
 
 #[derive(Accounts)]
+#[instruction(id: u64)]
 pub struct InitializeKol<'info> {
     #[account(
         init,
@@ -10,7 +11,7 @@ pub struct InitializeKol<'info> {
         space = 8 + Kol::INIT_SPACE,
         seeds = [
             b"kol",
-            kol.id.to_le_bytes().as_ref()
+            id.to_le_bytes().as_ref()
         ],
         bump
     )]
@@ -21,6 +22,7 @@ pub struct InitializeKol<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_kol(
     ctx: Context<InitializeKol>,
     id: u64,
@@ -43,6 +45,7 @@ pub fn initialize_kol(
     kol.account_creation = account_creation;
     kol.followers = followers;
     kol.ecosystem = ecosystem;
+    kol.payout_wallet = None;
     kol.bump = ctx.bumps.kol;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::errors::SoddleError;
+use crate::states::{fee_vault::FeeVault, game_metrics::GameMetrics, token_metrics::TokenMetrics};
+
+// One vault per mint, created by the authority before any competition charges in it
+#[derive(Accounts)]
+pub struct InitializeTokenVault<'info> {
+    #[account(
+        seeds = [b"game_metrics"],
+        bump = metrics.bump,
+        constraint = metrics.authority == authority.key() @ SoddleError::UnauthorizedAuthority
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(
+        seeds = [b"fee_vault"],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mint::token_program = token_program)]
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"token_vault", fee_mint.key().as_ref()],
+        bump,
+        token::mint = fee_mint,
+        token::authority = fee_vault,
+        token::token_program = token_program
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenMetrics::INIT_SPACE,
+        seeds = [b"token_metrics", fee_mint.key().as_ref()],
+        bump
+    )]
+    pub token_metrics: Account<'info, TokenMetrics>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Fee accounting credits the amount the player was charged, so the vault must receive
+// exactly that: no withheld transfer fee, and no hook that could move or block tokens
+const UNSUPPORTED_EXTENSIONS: [ExtensionType; 2] = [ExtensionType::TransferFeeConfig, ExtensionType::TransferHook];
+
+pub fn initialize_token_vault(ctx: Context<InitializeTokenVault>) -> Result<()> {
+    let mint_info = ctx.accounts.fee_mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+    let extensions = mint.get_extension_types()?;
+    require!(
+        !UNSUPPORTED_EXTENSIONS.iter().any(|extension| extensions.contains(extension)),
+        SoddleError::UnsupportedMintExtension
+    );
+
+    let token_metrics = &mut ctx.accounts.token_metrics;
+    token_metrics.mint = ctx.accounts.fee_mint.key();
+    token_metrics.total_fees_collected = 0;
+    token_metrics.total_refunded = 0;
+    token_metrics.total_withdrawn = 0;
+    token_metrics.bump = ctx.bumps.token_metrics;
+
+    Ok(())
+}
@@ -1,6 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash::hash;
-
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::contexts::verify_signature::verify_oracle_signature;
+use crate::constants::*;
+use crate::errors::SoddleError;
+use crate::events::{AttemptCompleted, CompletionReason, GuessAttempted};
+use crate::states::{competition::Competition, game_session::GameSession, guess_verifier::GuessVerifier};
+use crate::scoring;
+use crate::states::guess_record::GuessRecord;
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MakeGuess<'info> {
     #[account(
@@ -12,15 +21,19 @@ pub struct MakeGuess<'info> {
             game_session.start_time.to_le_bytes().as_ref()
         ],
         bump = game_session.bump,
-        constraint = !game_session.is_expired() @ GameError::SessionExpired,
-        constraint = game_session.player == player.key() @ GameError::InvalidPlayer,
-        has_one = player @ GameError::InvalidPlayer,
+        constraint = !game_session.is_expired(Clock::get()?.unix_timestamp) @ SoddleError::SessionExpired,
+        constraint = game_session.player == player.key() @ SoddleError::InvalidPlayer,
+        has_one = player @ SoddleError::InvalidPlayer,
+        // Room for this guess's record, returned with the rest of the rent on close
+        realloc = game_session.to_account_info().data_len() + GameSession::GUESS_SPACE,
+        realloc::payer = player,
+        realloc::zero = false,
     )]
     pub game_session: Account<'info, GameSession>,
 
     #[account(
         seeds = [
-            b"guess_verifier",
+            game_session.verifier_seed(),
             game_session.key().as_ref(),
         ],
         bump = verifier.bump,
@@ -28,12 +41,29 @@ pub struct MakeGuess<'info> {
     pub verifier: Account<'info, GuessVerifier>,
 
     #[account(
-        constraint = oracle_authority.key() == verifier.oracle_authority @ GameError::InvalidOracle
+        seeds = [
+            b"competition",
+            competition.game_state.as_ref(),
+            game_session.competition_id.to_le_bytes().as_ref()
+        ],
+        bump = competition.bump,
+        address = game_session.competition @ SoddleError::InvalidCompetition
+    )]
+    pub competition: Account<'info, Competition>,
+
+    #[account(
+        constraint = oracle_authority.key() == verifier.oracle_authority @ SoddleError::InvalidOracle
     )]
     pub oracle_authority: Signer<'info>,
 
     #[account(mut)]
     pub player: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, read to find the oracle's Ed25519 verification
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn make_guess(
@@ -43,75 +73,95 @@ pub fn make_guess(
 ) -> Result<()> {
     let game_session = &mut ctx.accounts.game_session;
     let current_time = Clock::get()?.unix_timestamp;
+    let session_key = game_session.key();
+    let competition_id = game_session.competition_id;
+    let game_type = game_session.game_type;
 
     // 1. Validate game session state
     let current_attempt_index = game_session.current_attempt
         .checked_sub(1)
-        .ok_or(GameError::GameNotStarted)? as usize;
+        .ok_or(SoddleError::GameNotStarted)? as usize;
 
-    let current_attempt = &mut game_session.attempts[current_attempt_index];
-    
-    require!(!current_attempt.completed, GameError::AttemptAlreadyCompleted);
+    let current_attempt = &game_session.attempts[current_attempt_index];
 
-    // 2. Validate attempt hasn't exceeded limits
-    let elapsed_time = current_time
-        .checked_sub(current_attempt.timestamp)
-        .ok_or(GameError::InvalidTimestamp)?;
+    require!(!current_attempt.completed, SoddleError::AttemptAlreadyCompleted);
 
-    require!(elapsed_time < ATTEMPT_TIME_LIMIT, GameError::AttemptTimedOut);
-    require!(current_attempt.guesses < MAX_GUESSES, GameError::TooManyGuesses);
+    // 2. Validate attempt hasn't exceeded limits
+    require!(
+        !scoring::is_attempt_timed_out(current_attempt.timestamp, current_time),
+        SoddleError::AttemptTimedOut
+    );
+    require!(current_attempt.guesses < MAX_GUESSES, SoddleError::TooManyGuesses);
 
     // 3. Verify guess with oracle
+    let guess_hash = hash(guess.as_bytes()).to_bytes();
     let message = [
-        &hash(guess.as_bytes()).to_bytes(),
-        &ctx.accounts.verifier.answer_hash
+        &guess_hash[..],
+        &ctx.accounts.verifier.answer_hash[..]
     ].concat();
 
     let is_valid_signature = verify_oracle_signature(
+        &ctx.accounts.instructions,
         &oracle_signature,
         &message,
         &ctx.accounts.oracle_authority.key()
     );
-    require!(is_valid_signature, GameError::InvalidOracleSignature);
+    require!(is_valid_signature, SoddleError::InvalidOracleSignature);
+
+    let is_correct = guess_hash == ctx.accounts.verifier.answer_hash;
 
-    let is_correct = hash(guess.as_bytes()).to_bytes() == ctx.accounts.verifier.answer_hash;
+    // 4. Score the guess; the same breakdown is stored and emitted
+    let breakdown = game_session.calculate_current_score(
+        &ctx.accounts.competition.scoring_rule,
+        &ctx.accounts.competition.score_model,
+        current_time,
+        is_correct,
+    );
 
-    // 4. Update attempt state
+    let current_attempt = &mut game_session.attempts[current_attempt_index];
     current_attempt.guesses = current_attempt.guesses
         .checked_add(1)
-        .ok_or(GameError::NumericOverflow)?;
-
-    // 5. Calculate and update score using the existing method
-    current_attempt.score = game_session.calculate_current_score(current_time, !is_correct);
-
-    // Calculate these values for the event emission
-    let elapsed_time = current_time - current_attempt.timestamp;
-    let time_periods = elapsed_time / TIME_PENALTY_INTERVAL;
-    let time_deduction = (time_periods as u32).saturating_mul(TIME_PENALTY);
-    let wrong_guess_penalty = if !is_correct { WRONG_GUESS_PENALTY } else { 0 };
-
-    // 6. Check if attempt should be completed
-    let should_end = is_correct 
-        || current_attempt.guesses >= MAX_GUESSES 
-        || elapsed_time >= ATTEMPT_TIME_LIMIT;
-
-    // 7. Emit guess event
-    emit!(GuessAttempted {
+        .ok_or(SoddleError::NumericOverflow)?;
+    let score_delta = i64::from(breakdown.final_score) - i64::from(current_attempt.score);
+    current_attempt.history.push(GuessRecord {
+        guess_hash,
+        timestamp: current_time,
+        score_delta: i32::try_from(score_delta).map_err(|_| SoddleError::NumericOverflow)?,
+        correct: is_correct,
+    });
+    current_attempt.score = breakdown.final_score;
+
+    // 5. Check if attempt should be completed
+    let out_of_guesses = current_attempt.guesses >= MAX_GUESSES;
+    let out_of_time = scoring::is_attempt_timed_out(current_attempt.timestamp, current_time);
+    let should_end = is_correct || out_of_guesses || out_of_time;
+
+    // 6. Emit guess event
+    emit_cpi!(GuessAttempted {
+        version: EVENT_SCHEMA_VERSION,
+        session: session_key,
+        competition_id,
+        game_type,
         player: ctx.accounts.player.key(),
         attempt_index: current_attempt_index as u8,
         guess_number: current_attempt.guesses,
         current_score: current_attempt.score,
         was_correct: is_correct,
         timestamp: current_time,
-        time_deduction,
-        wrong_guess_penalty,
+        time_deduction: breakdown.time_deduction,
+        wrong_guess_penalty: breakdown.wrong_guess_deduction,
+        speed_bonus: breakdown.bonus,
     });
 
-    // 8. Handle attempt completion if needed
+    // 7. Handle attempt completion if needed
     if should_end {
         current_attempt.completed = true;
         
-        emit!(AttemptCompleted {
+        emit_cpi!(AttemptCompleted {
+            version: EVENT_SCHEMA_VERSION,
+            session: session_key,
+            competition_id,
+            game_type,
             player: ctx.accounts.player.key(),
             attempt_index: current_attempt_index as u8,
             final_score: current_attempt.score,
@@ -120,7 +170,7 @@ pub fn make_guess(
             timestamp: current_time,
             reason: if is_correct {
                 CompletionReason::CorrectGuess
-            } else if current_attempt.guesses >= MAX_GUESSES {
+            } else if out_of_guesses {
                 CompletionReason::MaxGuesses
             } else {
                 CompletionReason::TimeExpired
@@ -0,0 +1,57 @@
+pub mod buy_bonus_attempt;
+pub mod buy_season_pass;
+pub mod buy_streak_freeze;
+pub mod claim_badge;
+pub mod claim_kol_rewards;
+pub mod claim_referral_rewards;
+pub mod close_practice_session;
+pub mod create_badge_mint;
+pub mod end_game_session;
+pub mod expire_game_session;
+pub mod initialize_competition;
+pub mod initialize_game_metrics;
+pub mod initialize_game_session;
+pub mod initialize_game_state;
+pub mod initialize_guess_verifier;
+pub mod initialize_kol;
+pub mod initialize_token_vault;
+pub mod make_guess;
+pub mod register_referrer;
+pub mod set_allowlist_root;
+pub mod set_bonus_attempt_config;
+pub mod set_fee_split;
+pub mod set_kol_payout_wallet;
+pub mod set_season_pass_config;
+pub mod set_streak_config;
+pub mod start_game_session;
+pub mod token_fee;
+pub mod verify_signature;
+pub mod withdraw_fees;
+
+pub use buy_bonus_attempt::*;
+pub use buy_season_pass::*;
+pub use buy_streak_freeze::*;
+pub use claim_badge::*;
+pub use claim_kol_rewards::*;
+pub use claim_referral_rewards::*;
+pub use close_practice_session::*;
+pub use create_badge_mint::*;
+pub use end_game_session::*;
+pub use expire_game_session::*;
+pub use initialize_competition::*;
+pub use initialize_game_metrics::*;
+pub use initialize_game_session::*;
+pub use initialize_game_state::*;
+pub use initialize_guess_verifier::*;
+pub use initialize_kol::*;
+pub use initialize_token_vault::*;
+pub use make_guess::*;
+pub use register_referrer::*;
+pub use set_allowlist_root::*;
+pub use set_bonus_attempt_config::*;
+pub use set_fee_split::*;
+pub use set_kol_payout_wallet::*;
+pub use set_season_pass_config::*;
+pub use set_streak_config::*;
+pub use start_game_session::*;
+pub use withdraw_fees::*;
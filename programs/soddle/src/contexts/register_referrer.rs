@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_REFERRAL_DEPTH;
+use crate::errors::SoddleError;
+use crate::states::referral::Referral;
+
+// The Referral accounts of the referrer's own referrers follow as remaining accounts
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", player.key().as_ref()],
+        bump,
+        constraint = referrer != player.key() @ SoddleError::SelfReferral
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", referrer.as_ref()],
+        bump
+    )]
+    pub referrer_referral: Account<'info, Referral>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Walks up the referral chain from `next`, the referrer's own referrer, and fails if
+/// it reaches `player`. `chain` holds the Referral account of every referrer on the way,
+/// in order; chains longer than MAX_REFERRAL_DEPTH are refused rather than walked.
+fn check_referral_chain(player: Pubkey, mut next: Option<Pubkey>, chain: &[AccountInfo]) -> Result<()> {
+    let mut chain = chain.iter();
+    let mut depth = 0;
+    while let Some(ancestor) = next {
+        require!(ancestor != player, SoddleError::ReferralCycle);
+        require!(depth < MAX_REFERRAL_DEPTH, SoddleError::InvalidReferralChain);
+
+        let info = chain.next().ok_or(SoddleError::InvalidReferralChain)?;
+        require!(*info.owner == crate::ID, SoddleError::InvalidReferralChain);
+        let referral = Referral::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let address = Pubkey::create_program_address(
+            &[b"referral", ancestor.as_ref(), &[referral.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| SoddleError::InvalidReferralChain)?;
+        require!(
+            info.key() == address && referral.player == ancestor,
+            SoddleError::InvalidReferralChain
+        );
+
+        next = referral.referrer;
+        depth += 1;
+    }
+    Ok(())
+}
+
+pub fn register_referrer(ctx: Context<RegisterReferrer>, referrer: Pubkey) -> Result<()> {
+    let player = ctx.accounts.player.key();
+    check_referral_chain(player, ctx.accounts.referrer_referral.referrer, ctx.remaining_accounts)?;
+
+    let referral = &mut ctx.accounts.referral;
+    referral.open(player, ctx.bumps.referral);
+    require!(referral.referrer.is_none(), SoddleError::ReferrerAlreadySet);
+    referral.referrer = Some(referrer);
+
+    let referrer_referral = &mut ctx.accounts.referrer_referral;
+    referrer_referral.open(referrer, ctx.bumps.referrer_referral);
+    referrer_referral.referees = referrer_referral.referees
+        .checked_add(1)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::errors::SoddleError;
+use crate::states::{competition::Competition, game_state::GameState};
+
+#[derive(Accounts)]
+pub struct SetAllowlistRoot<'info> {
+    // Only the authority whose game state holds the competition
+    #[account(
+        seeds = [b"game_state", authority.key().as_ref()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"competition",
+            game_state.key().as_ref(),
+            competition.id.to_le_bytes().as_ref()
+        ],
+        bump = competition.bump
+    )]
+    pub competition: Account<'info, Competition>,
+
+    pub authority: Signer<'info>,
+}
+
+/// None opens the competition to everyone again.
+pub fn set_allowlist_root(ctx: Context<SetAllowlistRoot>, allowlist_root: Option<[u8; 32]>) -> Result<()> {
+    let competition = &mut ctx.accounts.competition;
+    // Sessions opened under the old list are not rechecked, so it is fixed once play begins
+    require!(
+        Clock::get()?.unix_timestamp < competition.start_time,
+        SoddleError::CompetitionStarted
+    );
+    competition.allowlist_root = allowlist_root;
+
+    Ok(())
+}
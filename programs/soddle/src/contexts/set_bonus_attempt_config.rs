@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::errors::SoddleError;
+use crate::states::{game_config::GameConfig, game_metrics::GameMetrics};
+
+#[derive(Accounts)]
+pub struct SetBonusAttemptConfig<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [b"game_config"],
+        bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        seeds = [b"game_metrics"],
+        bump = metrics.bump,
+        constraint = metrics.authority == authority.key() @ SoddleError::UnauthorizedAuthority
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_bonus_attempt_config(
+    ctx: Context<SetBonusAttemptConfig>,
+    bonus_attempt_price: u64,
+    bonus_attempt_price_step_bps: u16,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.bonus_attempt_price = bonus_attempt_price;
+    config.bonus_attempt_price_step_bps = bonus_attempt_price_step_bps;
+    config.bump = ctx.bumps.config;
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::errors::SoddleError;
+use crate::fees;
+use crate::states::{game_config::GameConfig, game_metrics::GameMetrics};
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [b"game_config"],
+        bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        seeds = [b"game_metrics"],
+        bump = metrics.bump,
+        constraint = metrics.authority == authority.key() @ SoddleError::UnauthorizedAuthority
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Applies to deposits made from now on; open sessions keep the split they were charged with
+pub fn set_fee_split(
+    ctx: Context<SetFeeSplit>,
+    treasury_share_bps: u16,
+    prize_pool_share_bps: u16,
+    kol_share_bps: u16,
+    referrer_share_bps: u16,
+) -> Result<()> {
+    require!(
+        fees::is_valid_split(treasury_share_bps, prize_pool_share_bps, kol_share_bps, referrer_share_bps),
+        SoddleError::InvalidFeeSplit
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.treasury_share_bps = treasury_share_bps;
+    config.prize_pool_share_bps = prize_pool_share_bps;
+    config.kol_share_bps = kol_share_bps;
+    config.referrer_share_bps = referrer_share_bps;
+    config.bump = ctx.bumps.config;
+
+    Ok(())
+}
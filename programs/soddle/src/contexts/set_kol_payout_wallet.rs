@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::errors::SoddleError;
+use crate::states::{game_metrics::GameMetrics, kol::Kol};
+
+#[derive(Accounts)]
+pub struct SetKolPayoutWallet<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"kol",
+            kol.id.to_le_bytes().as_ref()
+        ],
+        bump = kol.bump
+    )]
+    pub kol: Account<'info, Kol>,
+
+    #[account(
+        seeds = [b"game_metrics"],
+        bump = metrics.bump,
+        constraint = metrics.authority == authority.key() @ SoddleError::UnauthorizedAuthority
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    pub authority: Signer<'info>,
+}
+
+// Rewards accrue with or without a wallet; `None` only pauses claims
+pub fn set_kol_payout_wallet(ctx: Context<SetKolPayoutWallet>, payout_wallet: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.kol.payout_wallet = payout_wallet;
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_DAILY_ATTEMPTS, MAX_SESSION_ATTEMPTS};
+use crate::errors::SoddleError;
+use crate::states::{game_config::GameConfig, game_metrics::GameMetrics};
+
+#[derive(Accounts)]
+pub struct SetSeasonPassConfig<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [b"game_config"],
+        bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        seeds = [b"game_metrics"],
+        bump = metrics.bump,
+        constraint = metrics.authority == authority.key() @ SoddleError::UnauthorizedAuthority
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_season_pass_config(
+    ctx: Context<SetSeasonPassConfig>,
+    season_pass_price: u64,
+    season_pass_duration: i64,
+    season_pass_bonus_attempts: u8,
+) -> Result<()> {
+    // A pass on sale has to last, and a session only has room for MAX_SESSION_ATTEMPTS
+    require!(
+        season_pass_price == 0 || season_pass_duration > 0,
+        SoddleError::InvalidSeasonPassConfig
+    );
+    require!(
+        MAX_DAILY_ATTEMPTS as u16 + season_pass_bonus_attempts as u16 <= MAX_SESSION_ATTEMPTS as u16,
+        SoddleError::InvalidSeasonPassConfig
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.season_pass_price = season_pass_price;
+    config.season_pass_duration = season_pass_duration;
+    config.season_pass_bonus_attempts = season_pass_bonus_attempts;
+    config.bump = ctx.bumps.config;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_STREAK_BONUS_BPS;
+use crate::errors::SoddleError;
+use crate::states::{game_config::GameConfig, game_metrics::GameMetrics};
+
+#[derive(Accounts)]
+pub struct SetStreakConfig<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [b"game_config"],
+        bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    #[account(
+        seeds = [b"game_metrics"],
+        bump = metrics.bump,
+        constraint = metrics.authority == authority.key() @ SoddleError::UnauthorizedAuthority
+    )]
+    pub metrics: Account<'info, GameMetrics>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_streak_config(
+    ctx: Context<SetStreakConfig>,
+    streak_bonus_bps: u16,
+    max_streak_bonus_bps: u16,
+    streak_freeze_price: u64,
+    max_streak_freezes: u8,
+) -> Result<()> {
+    require!(
+        max_streak_bonus_bps <= MAX_STREAK_BONUS_BPS && streak_bonus_bps <= max_streak_bonus_bps,
+        SoddleError::InvalidStreakConfig
+    );
+    // Freezes on sale can't be given away
+    require!(
+        max_streak_freezes == 0 || streak_freeze_price > 0,
+        SoddleError::InvalidStreakConfig
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.streak_bonus_bps = streak_bonus_bps;
+    config.max_streak_bonus_bps = max_streak_bonus_bps;
+    config.streak_freeze_price = streak_freeze_price;
+    config.max_streak_freezes = max_streak_freezes;
+    config.bump = ctx.bumps.config;
+
+    Ok(())
+}
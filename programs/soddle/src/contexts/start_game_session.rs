@@ -1,9 +1,26 @@
 use anchor_lang::prelude::*;
-use crate::states::{kol::Kol, game_session::GameSession, game_metrics::GameMetrics, kol::Kol, fee_vault::FeeVault};
+use crate::states::{kol::Kol, game_session::GameSession, game_metrics::GameMetrics, fee_vault::FeeVault};
+use crate::states::competition::Competition;
+use crate::states::token_metrics::TokenMetrics;
+use crate::states::game_config::GameConfig;
+use crate::states::fee_ledger::{FeeLedger, FeeParty};
+use crate::states::referral::Referral;
+use crate::states::voucher::{SignedVoucher, VoucherBitmap};
+use crate::states::season_pass::SeasonPass;
+use crate::constants::*;
+use crate::contexts::verify_signature::verify_oracle_signature;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::fees;
+use crate::contexts::token_fee;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::events::*;
 use crate::errors::*;
+use anchor_lang::system_program;
+use crate::states::game_attempt::GameAttempt;
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(competition_id: u64, start_time: i64, voucher: Option<SignedVoucher>)]
 pub struct StartGameSession<'info> {
     #[account(
         mut,
@@ -14,9 +31,8 @@ pub struct StartGameSession<'info> {
             start_time.to_le_bytes().as_ref()
         ],
         bump = game_session.bump,
-        constraint = !game_session.is_expired() @ GameError::SessionExpired,
-        constraint = game_session.player == player.key() @ GameError::InvalidPlayer,
-        constraint = game_session.can_start_new_attempt() @ GameError::MaxAttemptsReached
+        constraint = !game_session.is_expired(Clock::get()?.unix_timestamp) @ SoddleError::SessionExpired,
+        constraint = game_session.player == player.key() @ SoddleError::InvalidPlayer
     )]
     pub game_session: Account<'info, GameSession>,
     
@@ -26,10 +42,22 @@ pub struct StartGameSession<'info> {
             kol.id.to_le_bytes().as_ref()
         ],
         bump = kol.bump,
-        constraint = game_session.kol == kol.key() @ GameError::InvalidKol
+        constraint = game_session.kol == kol.key() @ SoddleError::InvalidKol
     )]
     pub kol: Account<'info, Kol>,
 
+    // Read for the scoring parameters announced with the attempt
+    #[account(
+        seeds = [
+            b"competition",
+            competition.game_state.as_ref(),
+            competition_id.to_le_bytes().as_ref()
+        ],
+        bump = competition.bump,
+        address = game_session.competition @ SoddleError::InvalidCompetition
+    )]
+    pub competition: Account<'info, Competition>,
+
     #[account(
         mut,
         seeds = [b"game_metrics"],
@@ -45,28 +73,163 @@ pub struct StartGameSession<'info> {
     )]
     pub fee_vault: Account<'info, FeeVault>,
 
+    #[account(
+        seeds = [b"game_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    // The fee's shares, one ledger per party and currency. Practice is free, so
+    // practice sessions leave them out
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"treasury_ledger", fee_vault.key().as_ref(), competition.fee_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub treasury_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"prize_pool_ledger", competition.key().as_ref(), competition.fee_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub prize_pool_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [b"kol_ledger", kol.key().as_ref(), competition.fee_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub kol_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    // Always passed, so a registered referrer can't be left out of the split
+    /// CHECK: The player's Referral PDA, which may not exist; only read by `Referral::referrer_of`
+    #[account(
+        seeds = [b"referral", player.key().as_ref()],
+        bump
+    )]
+    pub referral: UncheckedAccount<'info>,
+
+    // Given when the player has registered a referrer
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [
+            b"referrer_ledger",
+            Referral::referrer_of(&referral).unwrap_or_default().as_ref(),
+            competition.fee_mint.unwrap_or_default().as_ref()
+        ],
+        bump
+    )]
+    pub referrer_ledger: Option<Box<Account<'info, FeeLedger>>>,
+
+    // Only for season pass holders; a lapsed pass is ignored
+    #[account(
+        seeds = [b"season_pass", player.key().as_ref()],
+        bump = season_pass.bump
+    )]
+    pub season_pass: Option<Account<'info, SeasonPass>>,
+
     #[account(
         mut,
-        constraint = player.lamports() >= GAME_FEE @ SoddleError::InsufficientFunds
+        constraint = game_session.is_practice
+            || competition.fee_mint.is_some()
+            || player.lamports() >= GAME_FEE @ SoddleError::InsufficientFunds
     )]
     pub player: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    // Only for competitions that charge in an SPL token
+    #[account(
+        address = competition.fee_mint.unwrap_or_default() @ SoddleError::InvalidFeeMint,
+        mint::token_program = token_program
+    )]
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", competition.fee_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metrics", competition.fee_mint.unwrap_or_default().as_ref()],
+        bump = token_metrics.bump
+    )]
+    pub token_metrics: Option<Account<'info, TokenMetrics>>,
+
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key() @ SoddleError::InvalidPlayer
+    )]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    // Only for token-gated competitions: a token account of the player meeting the gate, and its mint
+    #[account(
+        constraint = gate_token_account.owner == player.key() @ SoddleError::TokenGateNotMet
+    )]
+    pub gate_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Only read by `TokenGate::is_met_by`, which checks its owner before trusting the data
+    pub gate_mint: Option<UncheckedAccount<'info>>,
+
+    // Only when redeeming a voucher
+    /// CHECK: Instructions sysvar, read to find the authority's Ed25519 verification of the voucher
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + VoucherBitmap::INIT_SPACE,
+        seeds = [
+            b"voucher_bitmap",
+            voucher.map(|signed| signed.voucher.bitmap_page()).unwrap_or_default().to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub voucher_bitmap: Option<Box<Account<'info, VoucherBitmap>>>,
 }
 
 pub fn start_game_session(
     ctx: Context<StartGameSession>,
-    competition_id: u64,
+    _competition_id: u64,
     start_time: i64,
+    voucher: Option<SignedVoucher>,
 ) -> Result<()> {
     let game_session = &mut ctx.accounts.game_session;
     let metrics = &mut ctx.accounts.metrics;
     let current_time = Clock::get()?.unix_timestamp;
     
+    let season_pass = ctx.accounts.season_pass
+        .as_deref()
+        .filter(|season_pass| season_pass.is_active(current_time));
+
     // Validate session state
+    let free_attempts = SeasonPass::free_attempts(season_pass, current_time);
     require!(
-        game_session.can_start_new_attempt(),
+        game_session.can_start_new_attempt(game_session.attempt_limit(free_attempts)),
         SoddleError::MaxAttemptsReached
     );
+    let is_bonus = game_session.current_attempt >= free_attempts;
+
+    if let Some(gate) = ctx.accounts.competition.token_gate {
+        let (Some(holding), Some(mint)) = (&ctx.accounts.gate_token_account, &ctx.accounts.gate_mint) else {
+            return err!(SoddleError::TokenGateNotMet);
+        };
+        require!(gate.is_met_by(holding, mint), SoddleError::TokenGateNotMet);
+    }
 
     // Validate timestamp
     require!(
@@ -74,25 +237,95 @@ pub fn start_game_session(
         SoddleError::InvalidStartTime
     );
 
-    // Transfer fee from player to fee vault
-    let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-            from: ctx.accounts.player.to_account_info(),
-            to: ctx.accounts.fee_vault.to_account_info(),
-        },
-    );
+    // Take the entry fee in the competition's currency, less any voucher discount;
+    // season passes and bonus attempts have been paid for up front, practice is free.
+    // Passes are bought in lamports, so they only stand in for lamport fees
+    let fee_mint = game_session.fee_mint;
+    let full_fee = match fee_mint {
+        None => GAME_FEE,
+        Some(_) => ctx.accounts.competition.fee_amount,
+    };
 
-    system_program::transfer(cpi_context, GAME_FEE)?;
+    let fee = match voucher {
+        _ if game_session.is_practice || (season_pass.is_some() && fee_mint.is_none()) || is_bonus => 0,
+        None => full_fee,
+        Some(SignedVoucher { voucher, signature }) => {
+            require!(
+                voucher.is_valid() && voucher.competition == ctx.accounts.competition.key(),
+                SoddleError::InvalidVoucher
+            );
+            require!(
+                voucher.player.iter().all(|player| *player == ctx.accounts.player.key()),
+                SoddleError::InvalidVoucher
+            );
+            require!(current_time < voucher.expires_at, SoddleError::VoucherExpired);
 
-    // Update metrics with checked arithmetic
-    metrics.total_fees_collected = metrics.total_fees_collected
-        .checked_add(GAME_FEE)
-        .ok_or(SoddleError::NumericOverflow)?;
-        
-    metrics.total_games_started = metrics.total_games_started
-        .checked_add(1)
-        .ok_or(SoddleError::NumericOverflow)?;
+            let (Some(instructions), Some(bitmap)) = (&ctx.accounts.instructions, ctx.accounts.voucher_bitmap.as_mut()) else {
+                return err!(SoddleError::MissingVoucherAccounts);
+            };
+            require!(
+                verify_oracle_signature(instructions, &signature, &voucher.message(), &metrics.authority),
+                SoddleError::InvalidVoucher
+            );
+
+            if bitmap.bump == 0 {
+                bitmap.page = voucher.bitmap_page();
+                bitmap.bump = ctx.bumps.voucher_bitmap.unwrap_or_default();
+            }
+            require!(bitmap.redeem(voucher.nonce), SoddleError::VoucherAlreadyUsed);
+
+            voucher.apply(full_fee)
+        }
+    };
+
+    if fee > 0 {
+        match fee_mint {
+            None => {
+                let cpi_context = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.fee_vault.to_account_info(),
+                    },
+                );
+
+                system_program::transfer(cpi_context, fee)?;
+
+                // Refunds take deposits back out of this total
+                ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault.total_collected
+                    .checked_add(fee)
+                    .ok_or(SoddleError::NumericOverflow)?;
+
+                // Update metrics with checked arithmetic
+                metrics.total_fees_collected = metrics.total_fees_collected
+                    .checked_add(fee)
+                    .ok_or(SoddleError::NumericOverflow)?;
+            }
+            Some(_) => {
+                let (Some(mint), Some(vault), Some(token_metrics), Some(from), Some(token_program)) = (
+                    &ctx.accounts.fee_mint,
+                    &ctx.accounts.token_vault,
+                    &mut ctx.accounts.token_metrics,
+                    &ctx.accounts.player_token_account,
+                    &ctx.accounts.token_program,
+                ) else {
+                    return err!(SoddleError::MissingTokenAccounts);
+                };
+
+                token_fee::deposit(token_program, mint, from, vault, &ctx.accounts.player, fee)?;
+
+                token_metrics.total_fees_collected = token_metrics.total_fees_collected
+                    .checked_add(fee)
+                    .ok_or(SoddleError::NumericOverflow)?;
+            }
+        }
+    }
+
+    if !game_session.is_practice {
+        metrics.total_games_started = metrics.total_games_started
+            .checked_add(1)
+            .ok_or(SoddleError::NumericOverflow)?;
+    }
 
     // Update game session state
     let attempt_index = game_session.current_attempt as usize;
@@ -101,6 +334,8 @@ pub fn start_game_session(
         guesses: 0,
         completed: false,
         timestamp: current_time,
+        is_bonus,
+        history: Vec::new(),
     };
 
     game_session.current_attempt = game_session.current_attempt
@@ -108,16 +343,63 @@ pub fn start_game_session(
         .ok_or(SoddleError::NumericOverflow)?;
         
     game_session.deposit = game_session.deposit
-        .checked_add(GAME_FEE)
+        .checked_add(fee)
         .ok_or(SoddleError::NumericOverflow)?;
 
+    // Split the fee between the ledgers. The shares stay pending until the session
+    // ends, as a refund takes them back.
+    if !game_session.is_practice {
+        let (Some(treasury_ledger), Some(prize_pool_ledger), Some(kol_ledger)) = (
+            ctx.accounts.treasury_ledger.as_mut(),
+            ctx.accounts.prize_pool_ledger.as_mut(),
+            ctx.accounts.kol_ledger.as_mut(),
+        ) else {
+            return err!(SoddleError::MissingFeeLedgers);
+        };
+
+        let referrer = Referral::referrer_of(&ctx.accounts.referral);
+        let split = fees::split_fee(fee, &ctx.accounts.config, referrer.is_some());
+        game_session.fee_split = game_session.fee_split
+            .checked_add(&split)
+            .ok_or(SoddleError::NumericOverflow)?;
+
+        treasury_ledger.open(FeeParty::Treasury, ctx.accounts.fee_vault.key(), fee_mint, ctx.bumps.treasury_ledger.unwrap_or_default());
+        treasury_ledger.add_pending(split.treasury)?;
+
+        prize_pool_ledger.open(FeeParty::PrizePool, ctx.accounts.competition.key(), fee_mint, ctx.bumps.prize_pool_ledger.unwrap_or_default());
+        prize_pool_ledger.add_pending(split.prize_pool)?;
+
+        kol_ledger.open(FeeParty::Kol, ctx.accounts.kol.key(), fee_mint, ctx.bumps.kol_ledger.unwrap_or_default());
+        kol_ledger.add_pending(split.kol)?;
+
+        if let Some(referrer) = referrer {
+            // A session keeps the referrer it was first charged for; registration is
+            // permanent, so later attempts find the same one
+            game_session.referrer = Some(referrer);
+            let Some(referrer_ledger) = ctx.accounts.referrer_ledger.as_mut() else {
+                return err!(SoddleError::MissingReferrerLedger);
+            };
+            referrer_ledger.open(FeeParty::Referrer, referrer, fee_mint, ctx.bumps.referrer_ledger.unwrap_or_default());
+            referrer_ledger.add_pending(split.referrer)?;
+        }
+    }
 
     // Emit event
-    emit!(GameSessionStarted {
+    emit_cpi!(GameSessionStarted {
+        version: EVENT_SCHEMA_VERSION,
+        session: game_session.key(),
+        competition_id: game_session.competition_id,
+        game_type: game_session.game_type,
         player: ctx.accounts.player.key(),
         kol: ctx.accounts.kol.key(),
         attempt: game_session.current_attempt,
         timestamp: current_time,
+        scoring_rule: ctx.accounts.competition.scoring_rule,
+        score_model: ctx.accounts.competition.score_model,
+        fee_mint,
+        fee_paid: fee,
+        is_bonus,
+        is_practice: game_session.is_practice,
     });
 
     Ok(())
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program::ID as ED25519_ID,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+// Ed25519 is a precompile and cannot be reached through CPI, so the client puts an
+// Ed25519 instruction directly in front of ours and we read it back from the
+// instructions sysvar. The runtime has already verified it; we only check that it
+// covered this signer, signature and message.
+pub fn verify_oracle_signature(
+    instructions: &AccountInfo,
+    signature: &[u8; 64],
+    message: &[u8],
+    oracle_pubkey: &Pubkey,
+) -> bool {
+    let Ok(current_index) = load_current_index_checked(instructions) else {
+        return false;
+    };
+    let Some(previous_index) = current_index.checked_sub(1) else {
+        return false;
+    };
+    let Ok(ix) = load_instruction_at_checked(previous_index as usize, instructions) else {
+        return false;
+    };
+    if ix.program_id != ED25519_ID {
+        return false;
+    }
+
+    // [num_signatures, padding, Ed25519SignatureOffsets (7 x u16), ...]
+    let data = &ix.data;
+    if data.len() < 16 || data[0] != 1 {
+        return false;
+    }
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_offset = read_u16(2) as usize;
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+
+    // All three must live inside the Ed25519 instruction itself
+    let same_instruction = [read_u16(4), read_u16(8), read_u16(14)]
+        .iter()
+        .all(|&index| index == u16::MAX);
+
+    same_instruction
+        && data.get(signature_offset..signature_offset + 64) == Some(&signature[..])
+        && data.get(public_key_offset..public_key_offset + 32) == Some(oracle_pubkey.as_ref())
+        && message_size == message.len()
+        && data.get(message_offset..message_offset + message_size) == Some(message)
+}
@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::states::game_metrics::GameMetrics;
+use crate::errors::SoddleError;
+use crate::states::fee_vault::FeeVault;
+use crate::states::token_metrics::TokenMetrics;
+use crate::contexts::token_fee;
+use crate::states::fee_ledger::FeeLedger;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct WithdrawFees<'info> {
@@ -19,6 +25,18 @@ pub struct WithdrawFees<'info> {
     )]
     pub fee_vault: Account<'info, FeeVault>,
 
+    // Only the treasury's share can be withdrawn; the rest belongs to other parties
+    #[account(
+        mut,
+        seeds = [
+            b"treasury_ledger",
+            fee_vault.key().as_ref(),
+            fee_mint.as_ref().map(|mint| mint.key()).unwrap_or_default().as_ref()
+        ],
+        bump = treasury_ledger.bump
+    )]
+    pub treasury_ledger: Box<Account<'info, FeeLedger>>,
+
     /// CHECK: This is the destination wallet that will receive the fees
     #[account(
         mut,
@@ -31,6 +49,32 @@ pub struct WithdrawFees<'info> {
     )]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
+
+    // Given together to withdraw from a mint's token vault instead of the lamport vault
+    #[account(mint::token_program = token_program)]
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_vault", token_vault.mint.as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metrics", token_metrics.mint.as_ref()],
+        bump = token_metrics.bump
+    )]
+    pub token_metrics: Option<Account<'info, TokenMetrics>>,
+
+    #[account(
+        mut,
+        constraint = receiver_token_account.owner == metrics.authority
+    )]
+    pub receiver_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 pub fn withdraw_fees(
@@ -39,13 +83,17 @@ pub fn withdraw_fees(
 ) -> Result<()> {
     require!(
         amount > 0,
-        GameError::InvalidWithdrawAmount
+        SoddleError::InvalidWithdrawAmount
     );
 
+    if ctx.accounts.fee_mint.is_some() {
+        return withdraw_token_fees(ctx, amount);
+    }
+
     let vault_balance = ctx.accounts.fee_vault.to_account_info().lamports();
     require!(
         amount <= vault_balance,
-        GameError::InsufficientFunds
+        SoddleError::InsufficientFunds
     );
 
     // Calculate minimum required balance for rent-exemption
@@ -54,34 +102,49 @@ pub fn withdraw_fees(
     
     require!(
         vault_balance.checked_sub(amount).unwrap() >= minimum_balance,
-        GameError::InsufficientFundsForRent
+        SoddleError::InsufficientFundsForRent
     );
 
-    // Transfer from fee vault to receiver using PDA signing
-    let seeds = [
-        b"fee_vault".as_ref(),
-        &[ctx.accounts.fee_vault.bump],
-    ];
-    let signer_seeds = &[&seeds[..]];
+    ctx.accounts.treasury_ledger.pay_out(amount)?;
 
-    let transfer_ix = system_program::Transfer {
-        from: ctx.accounts.fee_vault.to_account_info(),
-        to: ctx.accounts.receiver.to_account_info(),
-    };
+    // The vault is a program-owned data account, which the system program
+    // refuses to debit, so the lamports are moved directly
+    ctx.accounts.fee_vault.sub_lamports(amount)?;
+    ctx.accounts.receiver.add_lamports(amount)?;
+
+    // Withdrawals leave the vault like refunds do
+    ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault
+        .total_collected
+        .checked_sub(amount)
+        .ok_or(SoddleError::NumericOverflow)?;
+
+    Ok(())
+}
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        transfer_ix,
-        signer_seeds,
+fn withdraw_token_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    let (Some(mint), Some(vault), Some(token_metrics), Some(to), Some(token_program)) = (
+        &ctx.accounts.fee_mint,
+        &ctx.accounts.token_vault,
+        &mut ctx.accounts.token_metrics,
+        &ctx.accounts.receiver_token_account,
+        &ctx.accounts.token_program,
+    ) else {
+        return err!(SoddleError::MissingTokenAccounts);
+    };
+    require!(
+        vault.mint == mint.key() && token_metrics.mint == mint.key(),
+        SoddleError::InvalidFeeMint
     );
 
-    system_program::transfer(cpi_ctx, amount)?;
+    // Rent is held in the vault's lamports, not its token balance, so all of it can go
+    require!(amount <= vault.amount, SoddleError::InsufficientFunds);
 
-    // Update fee vault total collected
-    ctx.accounts.fee_vault.total_collected = ctx.accounts.fee_vault
-        .total_collected
+    ctx.accounts.treasury_ledger.pay_out(amount)?;
+    token_fee::pay_out(token_program, mint, vault, to, &ctx.accounts.fee_vault, amount)?;
+
+    token_metrics.total_withdrawn = token_metrics.total_withdrawn
         .checked_add(amount)
         .ok_or(SoddleError::NumericOverflow)?;
 
     Ok(())
-}
\ No newline at end of file
+}
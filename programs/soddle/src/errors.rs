@@ -50,8 +50,6 @@ pub enum SoddleError {
     InsufficientFunds,
     #[msg("Game session has expired")]
     SessionExpired,
-    #[msg("Invalid player")]
-    InvalidPlayer,
     #[msg("Invalid KOL account")]
     InvalidKol,
     #[msg("Game session not started")]
@@ -60,6 +58,86 @@ pub enum SoddleError {
     AttemptAlreadyCompleted,
     #[msg("Invalid guess count")]
     InvalidGuessCount,
-    #[msg("Numeric overflow occurred")]
-    NumericOverflow,
-}
\ No newline at end of file
+    #[msg("Maximum number of attempts reached")]
+    MaxAttemptsReached,
+    #[msg("Attempt has timed out")]
+    AttemptTimedOut,
+    #[msg("Too many guesses")]
+    TooManyGuesses,
+    #[msg("Invalid oracle")]
+    InvalidOracle,
+    #[msg("Invalid oracle signature")]
+    InvalidOracleSignature,
+    #[msg("Invalid start time")]
+    InvalidStartTime,
+    #[msg("Withdrawal would leave the fee vault below rent exemption")]
+    InsufficientFundsForRent,
+    #[msg("Invalid fee vault")]
+    InvalidFeeVault,
+    #[msg("Invalid score model")]
+    InvalidScoreModel,
+    #[msg("Invalid scoring rule parameters")]
+    InvalidScoringRule,
+    #[msg("Invalid streak bonus configuration")]
+    InvalidStreakConfig,
+    #[msg("No more streak freezes can be held")]
+    StreakFreezeLimitReached,
+    #[msg("Achievement not unlocked")]
+    BadgeNotEarned,
+    #[msg("Badge already claimed")]
+    BadgeAlreadyClaimed,
+    #[msg("Fee mint does not match the competition")]
+    InvalidFeeMint,
+    #[msg("Token fee accounts are missing")]
+    MissingTokenAccounts,
+    #[msg("Token fees need a mint and a positive amount")]
+    InvalidFeeAmount,
+    #[msg("Fee shares must add up to 10000 basis points")]
+    InvalidFeeSplit,
+    #[msg("Signer is not the payout wallet")]
+    InvalidPayoutWallet,
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+    #[msg("Players cannot refer themselves")]
+    SelfReferral,
+    #[msg("Referrer already registered")]
+    ReferrerAlreadySet,
+    #[msg("Players who have referred others cannot register a referrer")]
+    ReferralCycle,
+    #[msg("Referrer fee ledger is missing")]
+    MissingReferrerLedger,
+    #[msg("Invalid voucher")]
+    InvalidVoucher,
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    #[msg("Voucher already redeemed")]
+    VoucherAlreadyUsed,
+    #[msg("Voucher accounts are missing")]
+    MissingVoucherAccounts,
+    #[msg("Season passes are not on sale")]
+    SeasonPassUnavailable,
+    #[msg("Invalid season pass configuration")]
+    InvalidSeasonPassConfig,
+    #[msg("Bonus attempts are not on sale")]
+    BonusAttemptsUnavailable,
+    #[msg("No more attempts fit in the session")]
+    BonusAttemptLimitReached,
+    #[msg("Practice sessions stay out of fees and leaderboards")]
+    PracticeSession,
+    #[msg("Not a practice session")]
+    NotPracticeSession,
+    #[msg("Player does not hold the tokens this competition requires")]
+    TokenGateNotMet,
+    #[msg("Invalid token gate")]
+    InvalidTokenGate,
+    #[msg("Player is not on the competition's allowlist")]
+    NotOnAllowlist,
+    #[msg("Referral chain is incomplete, out of order or longer than MAX_REFERRAL_DEPTH")]
+    InvalidReferralChain,
+    #[msg("Paid attempts need the treasury, prize pool and KOL ledgers")]
+    MissingFeeLedgers,
+    #[msg("Fee mints cannot charge transfer fees or run transfer hooks")]
+    UnsupportedMintExtension,
+    #[msg("Game session has not expired yet")]
+    SessionNotExpired,
+}
@@ -1,15 +1,37 @@
 use anchor_lang::prelude::*;
+use crate::achievements::Achievement;
+use crate::states::score_model::ScoreModel;
+use crate::states::scoring_rule::ScoringRule;
+
+// Every event starts with the same header: `version` (see `EVENT_SCHEMA_VERSION`), the
+// `GameSession` PDA, its competition id and game type. Consumers should check `version`
+// before relying on any field that follows the header.
 
 #[event]
 pub struct GameSessionStarted {
+    pub version: u8,
+    pub session: Pubkey,
+    pub competition_id: u64,
+    pub game_type: u8,
     pub player: Pubkey,
     pub kol: Pubkey,
     pub attempt: u8,
     pub timestamp: i64,
+    // The competition's scoring parameters, so an attempt can be re-scored from its events alone
+    pub scoring_rule: ScoringRule,
+    pub score_model: ScoreModel,
+    pub fee_mint: Option<Pubkey>,  // None when the fee was paid in lamports
+    pub fee_paid: u64,
+    pub is_bonus: bool,  // Paid for with `buy_bonus_attempt`, leaderboards may leave it out
+    pub is_practice: bool,  // Practice sessions never end with `GameSessionEnded`
 }
 
 #[event]
 pub struct GuessAttempted {
+    pub version: u8,
+    pub session: Pubkey,
+    pub competition_id: u64,
+    pub game_type: u8,
     pub player: Pubkey,
     pub attempt_index: u8,
     pub guess_number: u32,
@@ -17,11 +39,16 @@ pub struct GuessAttempted {
     pub was_correct: bool,
     pub timestamp: i64,
     pub time_deduction: u32,
-    pub wrong_guess_penalty: u32,
+    pub wrong_guess_penalty: u32,  // Total wrong-guess deduction of the attempt so far
+    pub speed_bonus: u32,
 }
 
 #[event]
 pub struct GameSessionCompleted {
+    pub version: u8,
+    pub session: Pubkey,
+    pub competition_id: u64,
+    pub game_type: u8,
     pub player: Pubkey,
     pub kol: Pubkey,
     pub final_score: u32,
@@ -31,6 +58,10 @@ pub struct GameSessionCompleted {
 
 #[event]
 pub struct AttemptCompleted {
+    pub version: u8,
+    pub session: Pubkey,
+    pub competition_id: u64,
+    pub game_type: u8,
     pub player: Pubkey,
     pub attempt_index: u8,
     pub final_score: u32,
@@ -42,8 +73,11 @@ pub struct AttemptCompleted {
 
 #[event]
 pub struct GameSessionEnded {
-    pub player: Pubkey,
+    pub version: u8,
+    pub session: Pubkey,
     pub competition_id: u64,
+    pub game_type: u8,
+    pub player: Pubkey,
     pub total_attempts: u8,
     pub best_score: u32,
     pub total_score: u32,
@@ -52,11 +86,39 @@ pub struct GameSessionEnded {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct StreakUpdated {
+    pub version: u8,
+    pub session: Pubkey,
+    pub competition_id: u64,
+    pub game_type: u8,
+    pub player: Pubkey,
+    pub day: i64,  // Day index of the session's first attempt
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub bonus_bps: u32,  // Applied to the best score to give `GameSessionEnded::total_score`
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AchievementUnlocked {
+    pub version: u8,
+    pub session: Pubkey,
+    pub competition_id: u64,
+    pub game_type: u8,
+    pub player: Pubkey,
+    pub achievement: Achievement,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct NewHighScore {
+    pub version: u8,
+    pub session: Pubkey,
+    pub competition_id: u64,
+    pub game_type: u8,
     pub player: Pubkey,
     pub score: u32,
-    pub competition_id: u64,
     pub timestamp: i64,
 }
 
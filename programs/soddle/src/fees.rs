@@ -0,0 +1,40 @@
+use crate::constants::*;
+use crate::states::fee_split::FeeSplit;
+use crate::states::game_config::GameConfig;
+
+// Pure fee arithmetic, shared with off-chain tools like `scoring`.
+
+/// `amount * bps / 10_000`, rounded down.
+pub fn bps_of(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// Divides `amount` by the configured shares. Rounding dust, and the referrer's share
+/// when there is no referrer, go to the treasury, so the parts always add up to `amount`.
+pub fn split_fee(amount: u64, config: &GameConfig, has_referrer: bool) -> FeeSplit {
+    let prize_pool = bps_of(amount, config.prize_pool_share_bps);
+    let kol = bps_of(amount, config.kol_share_bps);
+    let referrer = if has_referrer {
+        bps_of(amount, config.referrer_share_bps)
+    } else {
+        0
+    };
+
+    FeeSplit {
+        treasury: amount - prize_pool - kol - referrer,
+        prize_pool,
+        kol,
+        referrer,
+    }
+}
+
+/// Price of an extra attempt when the player already bought `bought` that day: `base`,
+/// raised by `step_bps` for each of those, compounding. None on overflow.
+pub fn bonus_attempt_price(base: u64, step_bps: u16, bought: u8) -> Option<u64> {
+    (0..bought).try_fold(base, |price, _| price.checked_add(bps_of(price, step_bps)))
+}
+
+pub fn is_valid_split(treasury_bps: u16, prize_pool_bps: u16, kol_bps: u16, referrer_bps: u16) -> bool {
+    let total = treasury_bps as u32 + prize_pool_bps as u32 + kol_bps as u32 + referrer_bps as u32;
+    total == BPS_DENOMINATOR
+}
@@ -1,46 +1,113 @@
 use anchor_lang::prelude::*;
 
+pub mod achievements;
+pub mod allowlist;
+pub mod constants;
+pub mod contexts;
+pub mod errors;
+pub mod events;
+pub mod fees;
+pub mod scoring;
+pub mod states;
+
+// The handlers share their names with the modules that define them
+#[allow(ambiguous_glob_reexports)]
+pub use contexts::*;
+use achievements::Achievement;
+use states::score_model::ScoreModel;
+use states::scoring_rule::ScoringRule;
+use states::token_gate::TokenGate;
+use states::voucher::SignedVoucher;
+
 declare_id!("2y7L42gHKEBAFprVLJ9XFQuzxLdT9dmptdgQsNdcJ4SP");
 
 #[program]
 pub mod soddle_game {
     use super::*;
 
+    pub fn claim_badge(ctx: Context<ClaimBadge>, achievement: Achievement) -> Result<()> {
+        contexts::claim_badge::claim_badge(ctx, achievement)
+    }
+
+    pub fn claim_kol_rewards(ctx: Context<ClaimKolRewards>) -> Result<()> {
+        contexts::claim_kol_rewards::claim_kol_rewards(ctx)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        contexts::claim_referral_rewards::claim_referral_rewards(ctx)
+    }
+
+    pub fn create_badge_mint(
+        ctx: Context<CreateBadgeMint>,
+        achievement: Achievement,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        contexts::create_badge_mint::create_badge_mint(ctx, achievement, name, symbol, uri)
+    }
+
+    pub fn close_practice_session(ctx: Context<ClosePracticeSession>) -> Result<()> {
+        contexts::close_practice_session::close_practice_session(ctx)
+    }
+
     pub fn end_game_session(ctx: Context<EndGameSession>) -> Result<()> {
+        contexts::end_game_session::end_game_session(ctx)
+    }
 
+    pub fn expire_game_session(ctx: Context<ExpireGameSession>) -> Result<()> {
+        contexts::expire_game_session::expire_game_session(ctx)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_competition(
         ctx: Context<InitializeCompetition>,
         id: u64,
         start_time: i64,
         end_time: i64,
+        scoring_rule: ScoringRule,
+        score_model: ScoreModel,
+        fee_mint: Option<Pubkey>,
+        fee_amount: u64,
+        token_gate: Option<TokenGate>,
     ) -> Result<()> {
+        contexts::initialize_competition::initialize_competition(ctx, id, start_time, end_time, scoring_rule, score_model, fee_mint, fee_amount, token_gate)
     }
 
     pub fn initialize_metrics(
         ctx: Context<InitializeGameMetrics>
     ) -> Result<()> {
+        contexts::initialize_game_metrics::initialize_metrics(ctx)
     }
 
     pub fn initialize_game_session(
         ctx: Context<InitializeGameSession>,
         competition_id: u64,
         start_time: i64,
+        game_type: u8,
+        is_practice: bool,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
+        contexts::initialize_game_session::initialize_game_session(ctx, competition_id, start_time, game_type, is_practice, proof)
     }
 
 
     pub fn initialize_game_state(ctx: Context<InitializeGameState>) -> Result<()> {
+        contexts::initialize_game_state::initialize_game_state(ctx)
+    }
 
+    pub fn initialize_token_vault(ctx: Context<InitializeTokenVault>) -> Result<()> {
+        contexts::initialize_token_vault::initialize_token_vault(ctx)
     }
 
     pub fn initialize_verifier(
         ctx: Context<InitializeVerifier>, 
         answer_hash: [u8; 32]
     ) -> Result<()> {
+        contexts::initialize_guess_verifier::initialize_verifier(ctx, answer_hash)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_kol(
         ctx: Context<InitializeKol>,
         id: u64,
@@ -53,6 +120,7 @@ pub mod soddle_game {
         followers: u32,
         ecosystem: String,
     ) -> Result<()> {
+        contexts::initialize_kol::initialize_kol(ctx, id, name, age, country, pfp_type, pfp, account_creation, followers, ecosystem)
     }
 
     pub fn make_guess(
@@ -60,21 +128,89 @@ pub mod soddle_game {
         guess: String,
         oracle_signature: [u8; 64],
     ) -> Result<()> {
+        contexts::make_guess::make_guess(ctx, guess, oracle_signature)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, referrer: Pubkey) -> Result<()> {
+        contexts::register_referrer::register_referrer(ctx, referrer)
+    }
+
+    pub fn set_allowlist_root(
+        ctx: Context<SetAllowlistRoot>,
+        allowlist_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        contexts::set_allowlist_root::set_allowlist_root(ctx, allowlist_root)
+    }
+
+    pub fn set_fee_split(
+        ctx: Context<SetFeeSplit>,
+        treasury_share_bps: u16,
+        prize_pool_share_bps: u16,
+        kol_share_bps: u16,
+        referrer_share_bps: u16,
+    ) -> Result<()> {
+        contexts::set_fee_split::set_fee_split(ctx, treasury_share_bps, prize_pool_share_bps, kol_share_bps, referrer_share_bps)
+    }
+
+    pub fn set_kol_payout_wallet(
+        ctx: Context<SetKolPayoutWallet>,
+        payout_wallet: Option<Pubkey>,
+    ) -> Result<()> {
+        contexts::set_kol_payout_wallet::set_kol_payout_wallet(ctx, payout_wallet)
+    }
+
+    pub fn set_streak_config(
+        ctx: Context<SetStreakConfig>,
+        streak_bonus_bps: u16,
+        max_streak_bonus_bps: u16,
+        streak_freeze_price: u64,
+        max_streak_freezes: u8,
+    ) -> Result<()> {
+        contexts::set_streak_config::set_streak_config(ctx, streak_bonus_bps, max_streak_bonus_bps, streak_freeze_price, max_streak_freezes)
+    }
+
+    pub fn buy_streak_freeze(ctx: Context<BuyStreakFreeze>) -> Result<()> {
+        contexts::buy_streak_freeze::buy_streak_freeze(ctx)
+    }
+
+    pub fn set_season_pass_config(
+        ctx: Context<SetSeasonPassConfig>,
+        season_pass_price: u64,
+        season_pass_duration: i64,
+        season_pass_bonus_attempts: u8,
+    ) -> Result<()> {
+        contexts::set_season_pass_config::set_season_pass_config(ctx, season_pass_price, season_pass_duration, season_pass_bonus_attempts)
+    }
+
+    pub fn buy_season_pass(ctx: Context<BuySeasonPass>) -> Result<()> {
+        contexts::buy_season_pass::buy_season_pass(ctx)
+    }
+
+    pub fn set_bonus_attempt_config(
+        ctx: Context<SetBonusAttemptConfig>,
+        bonus_attempt_price: u64,
+        bonus_attempt_price_step_bps: u16,
+    ) -> Result<()> {
+        contexts::set_bonus_attempt_config::set_bonus_attempt_config(ctx, bonus_attempt_price, bonus_attempt_price_step_bps)
+    }
+
+    pub fn buy_bonus_attempt(ctx: Context<BuyBonusAttempt>) -> Result<()> {
+        contexts::buy_bonus_attempt::buy_bonus_attempt(ctx)
     }
 
     pub fn start_game_session(
         ctx: Context<StartGameSession>,
         competition_id: u64,
         start_time: i64,
+        voucher: Option<SignedVoucher>,
     ) -> Result<()> {
+        contexts::start_game_session::start_game_session(ctx, competition_id, start_time, voucher)
     }
 
     pub fn withdraw_fees(
         ctx: Context<WithdrawFees>, 
         amount: u64
     ) -> Result<()> {
+        contexts::withdraw_fees::withdraw_fees(ctx, amount)
     }
 }
-
-
-
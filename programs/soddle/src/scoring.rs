@@ -0,0 +1,171 @@
+use crate::constants::*;
+use crate::states::score_model::{ScoreModel, WrongGuessPenalty};
+use crate::states::scoring_rule::ScoringRule;
+
+// Pure scoring rules. Nothing in here touches syscalls or accounts: callers pass in
+// the timestamps, so the same functions run on-chain, in tests and in off-chain tools.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    pub base: u32,
+    pub time_deduction: u32,
+    pub wrong_guess_deduction: u32,
+    pub bonus: u32,
+    pub final_score: u32,
+}
+
+/// Seconds between `started_at` and `now`, clamped at zero if the clock is behind.
+pub fn elapsed(started_at: i64, now: i64) -> i64 {
+    now.saturating_sub(started_at).max(0)
+}
+
+pub fn is_session_expired(session_start: i64, now: i64) -> bool {
+    elapsed(session_start, now) >= SESSION_DURATION
+}
+
+/// Days since the epoch in UTC; two timestamps on the same calendar day share an index.
+pub fn day_index(timestamp: i64) -> i64 {
+    timestamp.div_euclid(SECONDS_PER_DAY)
+}
+
+/// Bonus for a streak of `streak` days: nothing on the first day, then `per_day_bps` for
+/// every further day, capped at `cap_bps`.
+pub fn streak_bonus_bps(streak: u32, per_day_bps: u16, cap_bps: u16) -> u32 {
+    let bonus = (streak.saturating_sub(1) as u64).saturating_mul(per_day_bps as u64);
+    bonus.min(cap_bps as u64) as u32
+}
+
+pub fn apply_bonus(score: u32, bonus_bps: u32) -> u32 {
+    let boosted = score as u64 * (BPS_DENOMINATOR as u64 + bonus_bps as u64) / BPS_DENOMINATOR as u64;
+    u32::try_from(boosted).unwrap_or(u32::MAX)
+}
+
+pub fn is_attempt_timed_out(attempt_start: i64, now: i64) -> bool {
+    elapsed(attempt_start, now) >= ATTEMPT_TIME_LIMIT
+}
+
+/// Deduction of the default linear curve: `TIME_PENALTY` per full `TIME_PENALTY_INTERVAL`.
+pub fn time_deduction(attempt_start: i64, now: i64) -> u32 {
+    linear_deduction(TIME_PENALTY_INTERVAL, TIME_PENALTY, elapsed(attempt_start, now))
+}
+
+pub fn linear_deduction(interval: i64, penalty: u32, elapsed: i64) -> u32 {
+    if interval <= 0 {
+        return 0;
+    }
+    let periods = elapsed.max(0) / interval;
+    u32::try_from(periods).unwrap_or(u32::MAX).saturating_mul(penalty)
+}
+
+/// What is left of `base` after `elapsed` seconds of halving every `half_life` seconds.
+/// Whole half-lives halve exactly; within one the value falls linearly towards the next half.
+pub fn exponential_remaining(base: u32, half_life: i64, elapsed: i64) -> u32 {
+    if half_life <= 0 {
+        return base;
+    }
+    let elapsed = elapsed.max(0);
+    let halvings = elapsed / half_life;
+    if halvings >= 32 {
+        return 0;
+    }
+    let whole = base >> halvings;
+    let fraction = (elapsed % half_life) as i128;
+    let lost = (whole / 2) as i128 * fraction / half_life as i128;
+    whole - lost as u32
+}
+
+/// Total deduction for `wrong_guesses` wrong guesses made so far in the attempt.
+/// `current_is_wrong` only matters for the flat model, which charges the latest guess alone.
+pub fn wrong_guess_deduction(penalty: WrongGuessPenalty, wrong_guesses: u32, current_is_wrong: bool) -> u32 {
+    match penalty {
+        WrongGuessPenalty::Flat => {
+            if current_is_wrong {
+                WRONG_GUESS_PENALTY
+            } else {
+                0
+            }
+        }
+        WrongGuessPenalty::Cumulative => wrong_guesses.saturating_mul(WRONG_GUESS_PENALTY),
+        WrongGuessPenalty::Escalating { step } => {
+            // n * P + step * (0 + 1 + ... + (n - 1))
+            let n = wrong_guesses as u64;
+            let pairs = n.saturating_mul(n.saturating_sub(1)) / 2;
+            let total = n
+                .saturating_mul(WRONG_GUESS_PENALTY as u64)
+                .saturating_add((step as u64).saturating_mul(pairs));
+            u32::try_from(total).unwrap_or(u32::MAX)
+        }
+    }
+}
+
+/// Points `rule` takes off `INITIAL_SCORE` for a guess `elapsed` seconds into the attempt.
+pub fn rule_time_deduction(rule: &ScoringRule, elapsed: i64) -> u32 {
+    match *rule {
+        ScoringRule::LinearDecay { interval, penalty } => linear_deduction(interval, penalty, elapsed),
+        ScoringRule::ExponentialDecay { half_life } => {
+            INITIAL_SCORE - exponential_remaining(INITIAL_SCORE, half_life, elapsed)
+        }
+        ScoringRule::GuessCountOnly | ScoringRule::FixedPoints { .. } => 0,
+    }
+}
+
+pub fn speed_bonus(model: &ScoreModel, attempt_start: i64, now: i64) -> u32 {
+    if model.speed_bonus == 0 || model.speed_bonus_window <= 0 {
+        return 0;
+    }
+    let remaining = model.speed_bonus_window.saturating_sub(elapsed(attempt_start, now));
+    if remaining <= 0 {
+        return 0;
+    }
+    let bonus = model.speed_bonus as i128 * remaining as i128 / model.speed_bonus_window as i128;
+    bonus as u32
+}
+
+/// Score of an attempt at `now`, right after a guess.
+///
+/// `previous_wrong_guesses` counts the wrong guesses made before this one; a correct
+/// guess always ends the attempt, so that is simply the number of earlier guesses.
+pub fn score_guess(
+    rule: &ScoringRule,
+    model: &ScoreModel,
+    attempt_start: i64,
+    now: i64,
+    previous_wrong_guesses: u32,
+    is_correct: bool,
+) -> ScoreBreakdown {
+    if let ScoringRule::FixedPoints { points } = *rule {
+        // A wrong guess forfeits the points, so the breakdown still adds up
+        let wrong_guess_deduction = if is_correct { 0 } else { points };
+        return ScoreBreakdown {
+            base: points,
+            wrong_guess_deduction,
+            final_score: points - wrong_guess_deduction,
+            ..ScoreBreakdown::default()
+        };
+    }
+
+    let wrong_guesses = previous_wrong_guesses.saturating_add(u32::from(!is_correct));
+    let elapsed = elapsed(attempt_start, now);
+
+    let base = INITIAL_SCORE;
+    let time_deduction = rule_time_deduction(rule, elapsed);
+    let wrong_guess_deduction = wrong_guess_deduction(model.wrong_guess_penalty, wrong_guesses, !is_correct);
+    let bonus = if is_correct {
+        speed_bonus(model, attempt_start, now)
+    } else {
+        0
+    };
+
+    let final_score = base
+        .saturating_sub(time_deduction)
+        .saturating_sub(wrong_guess_deduction)
+        .saturating_add(bonus);
+
+    ScoreBreakdown {
+        base,
+        time_deduction,
+        wrong_guess_deduction,
+        bonus,
+        final_score,
+    }
+}
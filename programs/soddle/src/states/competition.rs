@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::states::score_model::ScoreModel;
+use crate::states::scoring_rule::ScoringRule;
+use crate::states::token_gate::TokenGate;
 
 #[account]
 #[derive(InitSpace)]
@@ -7,5 +10,11 @@ pub struct Competition {
     pub start_time: i64,
     pub end_time: i64,
     pub game_state: Pubkey,  // Reference back to parent GameState
+    pub scoring_rule: ScoringRule,
+    pub score_model: ScoreModel,
+    pub fee_mint: Option<Pubkey>,  // None charges GAME_FEE in lamports
+    pub fee_amount: u64,  // Entry fee in base units of `fee_mint`
+    pub token_gate: Option<TokenGate>,  // Holdings checked at every attempt, None lets anyone play
+    pub allowlist_root: Option<[u8; 32]>,  // Merkle root of the invited players, see `allowlist`
     pub bump: u8,
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::errors::SoddleError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum FeeParty {
+    Treasury,   // Beneficiary is the fee vault
+    PrizePool,  // Beneficiary is the competition
+    Kol,        // Beneficiary is the featured KOL's account
+    Referrer,   // Beneficiary is the referrer's wallet
+}
+
+// One party's share of the fees in one currency. The funds stay in the fee vault or
+// the mint's token vault; the ledger bounds what its party may take out of them.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeLedger {
+    pub party: FeeParty,
+    pub beneficiary: Pubkey,
+    pub mint: Option<Pubkey>,  // None for lamports
+    pub pending: u64,  // Shares of sessions still open, which may yet be refunded
+    pub balance: u64,  // Settled and not paid out yet
+    pub total_earned: u64,
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+impl FeeLedger {
+    /// Fills in a ledger created by `init_if_needed`; a no-op on an existing one.
+    pub fn open(&mut self, party: FeeParty, beneficiary: Pubkey, mint: Option<Pubkey>, bump: u8) {
+        if self.beneficiary == Pubkey::default() {
+            self.party = party;
+            self.beneficiary = beneficiary;
+            self.mint = mint;
+            self.bump = bump;
+        }
+    }
+
+    /// Share of a session deposit, held back until the session ends.
+    pub fn add_pending(&mut self, amount: u64) -> Result<()> {
+        self.pending = self.pending.checked_add(amount).ok_or(SoddleError::NumericOverflow)?;
+        Ok(())
+    }
+
+    /// Releases a share added with `add_pending`: dropped if its deposit was refunded,
+    /// earned otherwise.
+    pub fn settle(&mut self, amount: u64, refunded: bool) -> Result<()> {
+        self.pending = self.pending.checked_sub(amount).ok_or(SoddleError::NumericOverflow)?;
+        if !refunded {
+            self.credit(amount)?;
+        }
+        Ok(())
+    }
+
+    /// Revenue that cannot be refunded, earned right away.
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.balance = self.balance.checked_add(amount).ok_or(SoddleError::NumericOverflow)?;
+        self.total_earned = self.total_earned.checked_add(amount).ok_or(SoddleError::NumericOverflow)?;
+        Ok(())
+    }
+
+    pub fn pay_out(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.balance, SoddleError::InsufficientFunds);
+        self.balance -= amount;
+        self.total_paid = self.total_paid.checked_add(amount).ok_or(SoddleError::NumericOverflow)?;
+        Ok(())
+    }
+}
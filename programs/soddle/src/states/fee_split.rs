@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+// How a deposit was divided between the fee ledgers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct FeeSplit {
+    pub treasury: u64,
+    pub prize_pool: u64,
+    pub kol: u64,
+    pub referrer: u64,
+}
+
+impl FeeSplit {
+    pub fn total(&self) -> u64 {
+        self.treasury + self.prize_pool + self.kol + self.referrer
+    }
+
+    pub fn checked_add(&self, other: &FeeSplit) -> Option<FeeSplit> {
+        Some(FeeSplit {
+            treasury: self.treasury.checked_add(other.treasury)?,
+            prize_pool: self.prize_pool.checked_add(other.prize_pool)?,
+            kol: self.kol.checked_add(other.kol)?,
+            referrer: self.referrer.checked_add(other.referrer)?,
+        })
+    }
+}
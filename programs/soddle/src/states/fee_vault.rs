@@ -4,6 +4,6 @@ use anchor_lang::prelude::*;
 #[derive(InitSpace)]
 pub struct FeeVault {
     pub authority: Pubkey,
-    pub total_collected: u64,
+    pub total_collected: u64,  // Lamports owed to the fee ledgers; refunds, withdrawals and claims take them back out
     pub bump: u8,
 }
\ No newline at end of file
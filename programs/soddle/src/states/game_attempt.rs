@@ -1,9 +1,33 @@
 use anchor_lang::prelude::*;
+use crate::constants::MAX_GUESSES;
+use crate::scoring;
+use crate::states::guess_record::GuessRecord;
+use crate::states::scoring_rule::ScoringRule;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, InitSpace)]
 pub struct GameAttempt {
     pub score: u32,
     pub guesses: u32,
     pub completed: bool,
     pub timestamp: i64,
-}
\ No newline at end of file
+    pub is_bonus: bool,  // Bought with `buy_bonus_attempt`, past the attempts the player gets for free
+    #[max_len(MAX_GUESSES)]
+    pub history: Vec<GuessRecord>,  // Every guess of the attempt, in order
+}
+
+impl GameAttempt {
+    // Only a correct guess ends an attempt with that guess as its last
+    pub fn is_solved(&self) -> bool {
+        self.completed && self.history.last().is_some_and(|guess| guess.correct)
+    }
+
+    // Solved with the first guess before `rule` took any points for time. A speed
+    // bonus can make up for a time deduction, so the guess's net score_delta doesn't tell
+    pub fn is_perfect(&self, rule: &ScoringRule) -> bool {
+        let [guess] = self.history.as_slice() else {
+            return false;
+        };
+        let elapsed = scoring::elapsed(self.timestamp, guess.timestamp);
+        self.is_solved() && scoring::rule_time_deduction(rule, elapsed) == 0
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+// Program-wide settings, changed by the metrics authority
+#[account]
+#[derive(InitSpace)]
+pub struct GameConfig {
+    pub streak_bonus_bps: u16,  // Score bonus per streak day after the first
+    pub max_streak_bonus_bps: u16,  // Cap of the streak bonus
+    pub streak_freeze_price: u64,  // Lamports per streak freeze
+    pub max_streak_freezes: u8,  // Freezes a profile can hold at once, 0 disables purchases
+    // Shares of every entry fee, summing to 10_000 once set; all zero sends everything to the treasury
+    pub treasury_share_bps: u16,
+    pub prize_pool_share_bps: u16,
+    pub kol_share_bps: u16,
+    pub referrer_share_bps: u16,
+    pub season_pass_price: u64,  // Lamports per pass, 0 disables purchases
+    pub season_pass_duration: i64,  // Seconds a purchase adds to the pass
+    pub season_pass_bonus_attempts: u8,  // Extra attempts per session for pass holders
+    pub bonus_attempt_price: u64,  // Lamports for a player's first attempt bought in a day, 0 disables purchases
+    pub bonus_attempt_price_step_bps: u16,  // Raise of each further attempt the player buys the same day
+    pub bump: u8,
+}
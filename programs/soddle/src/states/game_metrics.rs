@@ -7,5 +7,9 @@ pub struct GameMetrics {
     pub total_games_started: u64,
     pub authority: Pubkey,      // Program authority who can withdraw fees
     pub fee_vault: Pubkey,      // PDA that holds the fees
+    pub total_season_pass_revenue: u64,  // Not part of total_fees_collected
+    pub total_games_completed: u64,
+    pub highest_score: u32,
+    pub highest_scorer: Pubkey,
     pub bump: u8,
 }
\ No newline at end of file
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::states::game_attempt::GameAttempt;
+use crate::states::guess_record::GuessRecord;
+use crate::states::fee_split::FeeSplit;
 use crate::constants::*;
+use crate::scoring::{self, ScoreBreakdown};
+use crate::states::score_model::ScoreModel;
+use crate::states::scoring_rule::ScoringRule;
 
 #[account]
 #[derive(InitSpace)]
@@ -9,20 +14,50 @@ pub struct GameSession {
     pub start_time: i64,
     pub total_score: u32,
     pub deposit: u64,
+    pub fee_mint: Option<Pubkey>,  // Currency of `deposit`, fixed from the competition at initialization
+    pub fee_split: FeeSplit,  // `deposit` by fee ledger, settled when the session ends
+    pub referrer: Option<Pubkey>,  // Owner of the referrer ledger holding `fee_split.referrer`
     pub kol: Pubkey,  // Changed to store KOL's PDA address
     pub competition_id: u64,  // Changed to u64 to match Competition PDA
+    pub competition: Pubkey,  // The competition's PDA; `competition_id` alone matches any game state's competition
+    pub game_type: u8,  // 1..=GAME_TYPE_COUNT
+    pub is_practice: bool,  // Free, outside metrics, leaderboards and refunds; closed with `close_practice_session`
     pub current_attempt: u8,  // Tracks which attempt (1-3) the player is on
-    pub attempts: [GameAttempt; 3],  // Fixed array for the 3 possible daily attempts
+    pub bonus_attempts: u8,  // Bought on top of the daily and season pass attempts
+    pub attempts: [GameAttempt; MAX_SESSION_ATTEMPTS as usize],  // The daily attempts, then any a season pass adds
     pub bump: u8,
 }
 
 impl GameSession {
-    pub fn is_expired(&self) -> bool {
-        Clock::get().unwrap().unix_timestamp >= self.start_time + 24 * 60 * 60
+    // Space of a session before its first guess. Reserving every attempt's full history
+    // up front would take MAX_SESSION_ATTEMPTS * MAX_GUESSES guess records, about 5.4KB
+    // or 0.038 SOL of rent per session; `make_guess` grows the account by
+    // GUESS_SPACE instead, so players only lock rent for the guesses they make
+    pub const INITIAL_SPACE: usize = 8 + Self::INIT_SPACE
+        - MAX_SESSION_ATTEMPTS as usize * MAX_GUESSES as usize * GuessRecord::INIT_SPACE;
+    pub const GUESS_SPACE: usize = GuessRecord::INIT_SPACE;
+
+    // Practice answers come from their own verifier, so they never share one with a paid session
+    pub fn verifier_seed(&self) -> &'static [u8] {
+        if self.is_practice {
+            b"practice_verifier"
+        } else {
+            b"guess_verifier"
+        }
+    }
+
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        scoring::is_session_expired(self.start_time, current_time)
+    }
+
+    // `max_attempts` is MAX_DAILY_ATTEMPTS unless the player holds a season pass
+    pub fn can_start_new_attempt(&self, max_attempts: u8) -> bool {
+        self.current_attempt < max_attempts.min(MAX_SESSION_ATTEMPTS)
     }
 
-    pub fn can_start_new_attempt(&self) -> bool {
-        self.current_attempt < MAX_DAILY_ATTEMPTS
+    // The free attempts plus those bought, within the session's slots
+    pub fn attempt_limit(&self, free_attempts: u8) -> u8 {
+        free_attempts.saturating_add(self.bonus_attempts).min(MAX_SESSION_ATTEMPTS)
     }
 
     pub fn can_end_session(&self) -> bool {
@@ -42,49 +77,58 @@ impl GameSession {
         false
     }
 
-    pub fn calculate_current_score(&self, current_time: i64, is_wrong_guess: bool) -> u32 {
+    // Scores the guess being made now; `attempt.guesses` still counts the earlier,
+    // necessarily wrong, guesses of the attempt
+    pub fn calculate_current_score(
+        &self,
+        rule: &ScoringRule,
+        model: &ScoreModel,
+        current_time: i64,
+        is_correct: bool,
+    ) -> ScoreBreakdown {
         let current_attempt_index = self.current_attempt.saturating_sub(1) as usize;
         let attempt = &self.attempts[current_attempt_index];
-        
-        // Calculate time-based penalty
-        let elapsed_time = current_time - attempt.timestamp;
-        let time_periods = elapsed_time / TIME_PENALTY_INTERVAL;
-        let time_deduction = (time_periods as u32).saturating_mul(TIME_PENALTY);
-        
-        // Calculate wrong guess penalty if applicable
-        let wrong_guess_deduction = if is_wrong_guess {
-            WRONG_GUESS_PENALTY
-        } else {
-            0
-        };
 
-        // Calculate final score
-        INITIAL_SCORE
-            .saturating_sub(time_deduction)
-            .saturating_sub(wrong_guess_deduction)
+        scoring::score_guess(rule, model, attempt.timestamp, current_time, attempt.guesses, is_correct)
     }
 
-    pub fn should_end_game(&self) -> bool {
+    pub fn should_end_game(&self, current_time: i64) -> bool {
         let current_attempt_index = self.current_attempt.saturating_sub(1) as usize;
         let attempt = &self.attempts[current_attempt_index];
-        
-        let current_time = Clock::get().unwrap().unix_timestamp;
-        let time_expired = current_time - attempt.timestamp >= ATTEMPT_TIME_LIMIT;
+
+        let time_expired = scoring::is_attempt_timed_out(attempt.timestamp, current_time);
         let max_guesses_reached = attempt.guesses >= MAX_GUESSES;
-        
+
         time_expired || max_guesses_reached
     }
 
+    pub fn played_attempts(&self) -> &[GameAttempt] {
+        &self.attempts[..self.current_attempt as usize]
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.played_attempts().iter().any(GameAttempt::is_solved)
+    }
+
+    pub fn best_score(&self) -> u32 {
+        self.played_attempts().iter().map(|attempt| attempt.score).max().unwrap_or(0)
+    }
+
+    // The session's score: its best attempt with the streak bonus. Metrics, the
+    // player's profile and the events go by this one.
+    pub fn final_score(&self, streak_bonus_bps: u32) -> u32 {
+        scoring::apply_bonus(self.best_score(), streak_bonus_bps)
+    }
+
     pub fn should_refund_deposit(&self) -> bool {
-        // Example refund conditions:
-        // 1. Player got a correct guess within first 5 guesses
-        // 2. Or scored above certain threshold
-        let has_winning_attempt = self.attempts.iter().any(|attempt| {
-            attempt.completed && 
-            attempt.guesses <= 5 && 
+        // Refund conditions, judged on an attempt's own score so a streak
+        // bonus can't earn one:
+        // 1. Player completed an attempt within 5 guesses
+        // 2. And that attempt reached the score threshold
+        self.played_attempts().iter().any(|attempt| {
+            attempt.completed &&
+            attempt.guesses <= 5 &&
             attempt.score >= 800  // High score threshold
-        });
-
-        has_winning_attempt
+        })
     }
 }
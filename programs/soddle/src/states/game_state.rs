@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use crate::states::competition::Competition;
 
 #[account]
 #[derive(InitSpace)]
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct GuessRecord {
+    pub guess_hash: [u8; 32],  // hash of the guess as submitted
+    pub timestamp: i64,
+    pub score_delta: i32,  // Change of the attempt score caused by this guess
+    pub correct: bool,
+}
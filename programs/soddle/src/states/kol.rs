@@ -17,5 +17,6 @@ pub struct Kol {
     pub followers: u32,
     #[max_len(20)]
     pub ecosystem: String,
+    pub payout_wallet: Option<Pubkey>,  // Claims the KOL's fee share; set by the authority
     pub bump: u8,
 }
\ No newline at end of file
@@ -0,0 +1,20 @@
+pub mod competition;
+pub mod fee_ledger;
+pub mod fee_split;
+pub mod fee_vault;
+pub mod game_attempt;
+pub mod game_config;
+pub mod game_metrics;
+pub mod game_session;
+pub mod game_state;
+pub mod guess_record;
+pub mod guess_verifier;
+pub mod kol;
+pub mod player_profile;
+pub mod referral;
+pub mod score_model;
+pub mod scoring_rule;
+pub mod season_pass;
+pub mod token_gate;
+pub mod token_metrics;
+pub mod voucher;
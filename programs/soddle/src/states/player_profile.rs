@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_GUESSES;
+use crate::errors::SoddleError;
+use crate::states::game_session::GameSession;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    pub player: Pubkey,
+    pub games_played: u32,  // Ended sessions
+    pub wins: u32,  // Ended sessions with at least one solved attempt
+    pub total_winning_guesses: u64,  // Guesses of the counted solve of every win
+    pub best_score: u32,
+    pub total_fees_paid: u64,  // In lamports; sessions paid in a token are left out
+    pub total_refunds: u64,  // Same
+    pub guess_distribution: [u32; MAX_GUESSES as usize],  // Wins by guesses needed, index 0 = one guess
+    pub current_streak: u32,  // Consecutive days with a solved session, up to `last_win_day`
+    pub longest_streak: u32,
+    pub last_played_day: i64,  // Day index (see `scoring::day_index`) of the latest played session
+    pub last_win_day: i64,
+    pub streak_freezes: u8,  // Each one covers one missed day, consumed automatically
+    pub achievements: u64,  // Bitset of unlocked `Achievement`s
+    pub game_types_played: u8,  // Bit `game_type - 1` is set once that type was played
+    pub game_types_solved: u8,  // Same, once that type was solved
+    pub badges_claimed: u64,  // Bitset of `Achievement`s whose badge was minted
+    pub bonus_attempts_day: i64,  // Day index of the purchases counted in `bonus_attempts_bought`
+    pub bonus_attempts_bought: u8,  // Bought that day across all sessions, sets the next price
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    pub fn average_guesses(&self) -> Option<u64> {
+        self.total_winning_guesses.checked_div(self.wins as u64)
+    }
+
+    // Folds an ending session into the lifetime stats. A win counts the solve with the
+    // fewest guesses, so a session adds at most one entry to the distribution.
+    pub fn record_session(&mut self, game_session: &GameSession, final_score: u32, refund: u64) -> Result<()> {
+        self.games_played = self.games_played
+            .checked_add(1)
+            .ok_or(SoddleError::NumericOverflow)?;
+        // Amounts in different mints don't add up
+        if game_session.fee_mint.is_none() {
+            self.total_fees_paid = self.total_fees_paid
+                .checked_add(game_session.deposit)
+                .ok_or(SoddleError::NumericOverflow)?;
+            self.total_refunds = self.total_refunds
+                .checked_add(refund)
+                .ok_or(SoddleError::NumericOverflow)?;
+        }
+
+        self.best_score = self.best_score.max(final_score);
+
+        let played = game_session.played_attempts();
+
+        let fewest_guesses = played
+            .iter()
+            .filter(|attempt| attempt.is_solved())
+            .map(|attempt| attempt.guesses)
+            .min();
+        if let Some(guesses) = fewest_guesses {
+            self.wins = self.wins
+                .checked_add(1)
+                .ok_or(SoddleError::NumericOverflow)?;
+            self.total_winning_guesses = self.total_winning_guesses
+                .checked_add(guesses as u64)
+                .ok_or(SoddleError::NumericOverflow)?;
+            let bucket = (guesses.clamp(1, MAX_GUESSES) - 1) as usize;
+            self.guess_distribution[bucket] = self.guess_distribution[bucket].saturating_add(1);
+        }
+
+        Ok(())
+    }
+
+    // Bonus attempts bought on `day`; the count starts over every day
+    pub fn bonus_attempts_bought_on(&self, day: i64) -> u8 {
+        if self.bonus_attempts_day == day {
+            self.bonus_attempts_bought
+        } else {
+            0
+        }
+    }
+
+    pub fn record_bonus_attempt(&mut self, day: i64) -> Result<()> {
+        self.bonus_attempts_bought = self.bonus_attempts_bought_on(day)
+            .checked_add(1)
+            .ok_or(SoddleError::NumericOverflow)?;
+        self.bonus_attempts_day = day;
+        Ok(())
+    }
+
+    // Days without a win between the last win and `day`
+    fn missed_days(&self, day: i64) -> i64 {
+        day.saturating_sub(self.last_win_day).saturating_sub(1).max(0)
+    }
+
+    // Streak as of `day`: a streak survives until the end of the day after its last win,
+    // plus one day per streak freeze held
+    pub fn streak_on(&self, day: i64) -> u32 {
+        if self.current_streak > 0 && self.missed_days(day) <= self.streak_freezes as i64 {
+            self.current_streak
+        } else {
+            0
+        }
+    }
+
+    // Streak rules, for a session that had at least one attempt on `day`:
+    // - solving extends a streak whose last win was the day before, or starts a new one
+    // - missed days in between are covered by streak freezes, which are used up doing so
+    // - failing every attempt breaks the streak, unless the day was already won
+    // - a missed day that no freeze covers breaks it implicitly, see `streak_on`
+    // Sessions from before the last win day (ended late) leave the streak alone.
+    pub fn record_streak(&mut self, day: i64, solved: bool) {
+        self.last_played_day = self.last_played_day.max(day);
+        if self.current_streak > 0 && day < self.last_win_day {
+            return;
+        }
+
+        if solved {
+            let current = self.streak_on(day);
+            self.current_streak = if current > 0 && self.last_win_day == day {
+                current
+            } else if current > 0 {
+                self.streak_freezes -= self.missed_days(day) as u8;
+                current.saturating_add(1)
+            } else {
+                1
+            };
+            self.last_win_day = day;
+        } else if self.last_win_day != day {
+            self.current_streak = 0;
+        }
+
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+// A player's place in the referral graph. Created when the player registers a
+// referrer, or when someone registers them as theirs.
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub player: Pubkey,
+    pub referrer: Option<Pubkey>,  // Set once, never changed
+    pub referees: u32,  // Players who registered this one as their referrer
+    pub bump: u8,
+}
+
+impl Referral {
+    /// Fills in an account created by `init_if_needed`; a no-op on an existing one.
+    pub fn open(&mut self, player: Pubkey, bump: u8) {
+        if self.player == Pubkey::default() {
+            self.player = player;
+            self.bump = bump;
+        }
+    }
+
+    /// The referrer recorded in `referral`, a player's Referral PDA; None while the
+    /// player has no Referral account yet.
+    pub fn referrer_of(referral: &AccountInfo) -> Option<Pubkey> {
+        if *referral.owner != crate::ID {
+            return None;
+        }
+        let data = referral.try_borrow_data().ok()?;
+        Referral::try_deserialize(&mut &data[..]).ok()?.referrer
+    }
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::constants::{ATTEMPT_TIME_LIMIT, INITIAL_SCORE};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum WrongGuessPenalty {
+    Flat,                       // Only the latest wrong guess costs WRONG_GUESS_PENALTY
+    Cumulative,                 // Every wrong guess of the attempt costs WRONG_GUESS_PENALTY
+    Escalating { step: u32 },   // The n-th wrong guess costs WRONG_GUESS_PENALTY + (n - 1) * step
+}
+
+// How guesses are scored within an attempt, chosen per competition
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct ScoreModel {
+    pub wrong_guess_penalty: WrongGuessPenalty,
+    pub speed_bonus: u32,         // Awarded in full for an instant correct answer
+    pub speed_bonus_window: i64,  // Seconds over which the bonus shrinks linearly to 0
+}
+
+impl ScoreModel {
+    // A bonus needs a window to shrink over, and neither it nor a step may exceed INITIAL_SCORE
+    pub fn is_valid(&self) -> bool {
+        let step_is_valid = match self.wrong_guess_penalty {
+            WrongGuessPenalty::Escalating { step } => step <= INITIAL_SCORE,
+            WrongGuessPenalty::Flat | WrongGuessPenalty::Cumulative => true,
+        };
+        let bonus_is_valid = if self.speed_bonus == 0 {
+            self.speed_bonus_window == 0
+        } else {
+            self.speed_bonus <= INITIAL_SCORE && (1..=ATTEMPT_TIME_LIMIT).contains(&self.speed_bonus_window)
+        };
+        step_is_valid && bonus_is_valid
+    }
+}
+
+impl Default for ScoreModel {
+    // The original scoring: flat wrong-guess penalty, no bonus
+    fn default() -> Self {
+        ScoreModel {
+            wrong_guess_penalty: WrongGuessPenalty::Flat,
+            speed_bonus: 0,
+            speed_bonus_window: 0,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+// The time curve a competition scores on. The competition's ScoreModel handles
+// wrong guesses and the speed bonus on top, except for FixedPoints.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ScoringRule {
+    LinearDecay { interval: i64, penalty: u32 },  // `penalty` points lost every `interval` seconds
+    ExponentialDecay { half_life: i64 },          // Remaining score halves every `half_life` seconds
+    FixedPoints { points: u32 },                  // A solve is worth `points`, nothing else counts
+    GuessCountOnly,                               // No time decay, only guess penalties
+}
+
+impl ScoringRule {
+    // Periods fit within an attempt and points within INITIAL_SCORE, so no rule
+    // scores above the default one
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            ScoringRule::LinearDecay { interval, penalty } => {
+                (1..=ATTEMPT_TIME_LIMIT).contains(&interval) && penalty <= INITIAL_SCORE
+            }
+            ScoringRule::ExponentialDecay { half_life } => (1..=ATTEMPT_TIME_LIMIT).contains(&half_life),
+            ScoringRule::FixedPoints { points } => points <= INITIAL_SCORE,
+            ScoringRule::GuessCountOnly => true,
+        }
+    }
+}
+
+impl Default for ScoringRule {
+    // The original curve: TIME_PENALTY every TIME_PENALTY_INTERVAL
+    fn default() -> Self {
+        ScoringRule::LinearDecay {
+            interval: TIME_PENALTY_INTERVAL,
+            penalty: TIME_PENALTY,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_DAILY_ATTEMPTS;
+
+// Paid up front instead of per attempt, extended by buying again
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonPass {
+    pub player: Pubkey,
+    pub expires_at: i64,
+    pub bonus_attempts: u8,  // Attempts a session allows beyond MAX_DAILY_ATTEMPTS, as configured at purchase
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+impl SeasonPass {
+    pub fn is_active(&self, current_time: i64) -> bool {
+        current_time < self.expires_at
+    }
+
+    /// Attempts a session allows without buying more: the daily ones, plus the
+    /// pass's while it runs.
+    pub fn free_attempts(season_pass: Option<&Self>, current_time: i64) -> u8 {
+        let pass_attempts = season_pass
+            .filter(|season_pass| season_pass.is_active(current_time))
+            .map_or(0, |season_pass| season_pass.bonus_attempts);
+        MAX_DAILY_ATTEMPTS.saturating_add(pass_attempts)
+    }
+
+    /// Runs `duration` past the current expiry, or from now if it has lapsed.
+    pub fn extend(&mut self, current_time: i64, duration: i64) -> Option<i64> {
+        self.expires_at = self.expires_at.max(current_time).checked_add(duration)?;
+        Some(self.expires_at)
+    }
+}
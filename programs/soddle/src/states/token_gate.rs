@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use spl_token_group_interface::state::TokenGroupMember;
+use anchor_spl::token_interface::{get_mint_extension_data, TokenAccount};
+
+// What a player has to hold to enter a gated competition
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum TokenGate {
+    // At least `min_amount` base units of `mint`
+    Balance { mint: Pubkey, min_amount: u64 },
+    // Any token of a Token-2022 mint that is a member of the `collection` group
+    Collection { collection: Pubkey },
+}
+
+impl TokenGate {
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            TokenGate::Balance { min_amount, .. } => min_amount > 0,
+            TokenGate::Collection { .. } => true,
+        }
+    }
+
+    /// Whether `holding`, a token account of the player, meets the gate. `mint` is the
+    /// holding's mint, whose group membership a collection gate reads.
+    pub fn is_met_by(&self, holding: &TokenAccount, mint: &AccountInfo) -> bool {
+        match *self {
+            TokenGate::Balance { mint: required, min_amount } => {
+                holding.mint == required && holding.amount >= min_amount
+            }
+            TokenGate::Collection { collection } => {
+                // Membership is only trusted when Token-2022 wrote it
+                holding.amount > 0
+                    && holding.mint == mint.key()
+                    && *mint.owner == token_2022::ID
+                    && get_mint_extension_data::<TokenGroupMember>(mint)
+                        .is_ok_and(|member| member.group == collection)
+            }
+        }
+    }
+}
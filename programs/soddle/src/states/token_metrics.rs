@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+// Fee totals for one SPL mint. Lamport fees keep being counted on `GameMetrics` and
+// `FeeVault`; token fees sit in the `token_vault` account of their mint.
+#[account]
+#[derive(InitSpace)]
+pub struct TokenMetrics {
+    pub mint: Pubkey,
+    pub total_fees_collected: u64,
+    pub total_refunded: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::fees;
+
+// A promotion signed off-chain by the metrics authority, redeemed with `start_game_session`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Voucher {
+    pub player: Option<Pubkey>,  // None lets anyone redeem it, once
+    pub competition: Pubkey,
+    pub discount_bps: u16,  // Off the entry fee; BPS_DENOMINATOR makes the attempt free
+    pub expires_at: i64,
+    pub nonce: u64,  // Unique per voucher, marked in its `VoucherBitmap` when redeemed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignedVoucher {
+    pub voucher: Voucher,
+    pub signature: [u8; 64],
+}
+
+impl Voucher {
+    /// The bytes the authority signs: a domain tag, so no other signed message of the
+    /// program can pass for a voucher, then the Borsh encoding.
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = VOUCHER_DOMAIN.to_vec();
+        self.serialize(&mut message).unwrap();
+        message
+    }
+
+    pub fn bitmap_page(&self) -> u64 {
+        self.nonce / VOUCHERS_PER_BITMAP
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.discount_bps as u32 <= BPS_DENOMINATOR
+    }
+
+    /// What is left to pay of `fee`.
+    pub fn apply(&self, fee: u64) -> u64 {
+        fee.saturating_sub(fees::bps_of(fee, self.discount_bps))
+    }
+}
+
+// Redeemed nonces `page * VOUCHERS_PER_BITMAP ..` as bits
+#[account]
+#[derive(InitSpace)]
+pub struct VoucherBitmap {
+    pub page: u64,
+    pub bits: [u8; VOUCHERS_PER_BITMAP as usize / 8],
+    pub bump: u8,
+}
+
+impl VoucherBitmap {
+    /// Marks `nonce` as redeemed; false if it already was.
+    pub fn redeem(&mut self, nonce: u64) -> bool {
+        let bit = (nonce % VOUCHERS_PER_BITMAP) as usize;
+        let mask = 1u8 << (bit % 8);
+        if self.bits[bit / 8] & mask != 0 {
+            return false;
+        }
+        self.bits[bit / 8] |= mask;
+        true
+    }
+}
@@ -0,0 +1,199 @@
+mod common;
+
+use common::*;
+use soddle::errors::SoddleError;
+use soddle::states::{competition::Competition, game_metrics::GameMetrics, game_state::GameState, kol::Kol};
+use soddle::states::game_config::GameConfig;
+use soddle::constants::{ATTEMPT_TIME_LIMIT, GAME_FEE, INITIAL_SCORE, MAX_STREAK_BONUS_BPS};
+use soddle::states::score_model::{ScoreModel, WrongGuessPenalty};
+use soddle::states::scoring_rule::ScoringRule;
+use soddle_client::{instructions, pda};
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn initializes_game_state_and_competition() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+
+    env.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+    let game_state = pda::game_state(&authority).0;
+    let state: GameState = env.account(&game_state).await;
+    assert_eq!(state.last_update_time, env.now().await);
+
+    let now = env.now().await;
+    let ix = instructions::initialize_competition(
+        &authority,
+        &game_state,
+        COMPETITION_ID,
+        now + 60,
+        now + 3600,
+        ScoringRule::ExponentialDecay { half_life: 120 },
+        ScoreModel::default(),
+        None,
+        0,
+        None,
+    );
+    env.send(&[ix], &[]).await.unwrap();
+
+    let competition: Competition = env.account(&pda::competition(&game_state, COMPETITION_ID).0).await;
+    assert_eq!(competition.id, COMPETITION_ID);
+    assert_eq!(competition.start_time, now + 60);
+    assert_eq!(competition.end_time, now + 3600);
+    assert_eq!(competition.game_state, game_state);
+    assert_eq!(competition.scoring_rule, ScoringRule::ExponentialDecay { half_life: 120 });
+    assert_eq!(competition.score_model, ScoreModel::default());
+    assert_eq!(competition.fee_mint, None);
+}
+
+#[tokio::test]
+async fn rejects_invalid_competition_windows() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    env.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+    let game_state = pda::game_state(&authority).0;
+    let now = env.now().await;
+
+    let rule = ScoringRule::default();
+    let ix = instructions::initialize_competition(&authority, &game_state, 2, now + 100, now + 50, rule, ScoreModel::default(), None, 0, None);
+    assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidTimeRange);
+
+    let ix = instructions::initialize_competition(&authority, &game_state, 3, now - 10, now + 50, rule, ScoreModel::default(), None, 0, None);
+    assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::CompetitionStarted);
+}
+
+#[tokio::test]
+async fn only_the_game_state_authority_adds_competitions() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    env.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+    let game_state = pda::game_state(&authority).0;
+    let now = env.now().await;
+    let player = env.player.insecure_clone();
+
+    let ix = instructions::initialize_competition(
+        &player.pubkey(),
+        &game_state,
+        COMPETITION_ID,
+        now + 10,
+        now + 50,
+        ScoringRule::default(),
+        ScoreModel::default(),
+        None,
+        0,
+        None,
+    );
+    assert!(env.send(&[ix], &[&player]).await.is_err());
+    assert!(!env.exists(&pda::competition(&game_state, COMPETITION_ID).0).await);
+}
+
+#[tokio::test]
+async fn rejects_degenerate_scoring_rules() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    env.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+    let game_state = pda::game_state(&authority).0;
+    let now = env.now().await;
+
+    for (id, rule) in [
+        (2, ScoringRule::LinearDecay { interval: 0, penalty: 5 }),
+        (3, ScoringRule::ExponentialDecay { half_life: -1 }),
+        (4, ScoringRule::LinearDecay { interval: ATTEMPT_TIME_LIMIT + 1, penalty: 5 }),
+        (5, ScoringRule::LinearDecay { interval: 5, penalty: INITIAL_SCORE + 1 }),
+        (6, ScoringRule::ExponentialDecay { half_life: ATTEMPT_TIME_LIMIT + 1 }),
+        (7, ScoringRule::FixedPoints { points: INITIAL_SCORE + 1 }),
+    ] {
+        let ix = instructions::initialize_competition(&authority, &game_state, id, now + 10, now + 50, rule, ScoreModel::default(), None, 0, None);
+        assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidScoringRule);
+    }
+}
+
+#[tokio::test]
+async fn rejects_unbounded_score_models() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    env.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+    let game_state = pda::game_state(&authority).0;
+    let now = env.now().await;
+
+    let bonus = |speed_bonus, speed_bonus_window| ScoreModel { speed_bonus, speed_bonus_window, ..ScoreModel::default() };
+    for (id, model) in [
+        (2, bonus(INITIAL_SCORE + 1, 60)),
+        (3, bonus(100, 0)),
+        (4, bonus(100, ATTEMPT_TIME_LIMIT + 1)),
+        (5, ScoreModel { wrong_guess_penalty: WrongGuessPenalty::Escalating { step: INITIAL_SCORE + 1 }, ..ScoreModel::default() }),
+    ] {
+        let ix = instructions::initialize_competition(&authority, &game_state, id, now + 10, now + 50, ScoringRule::default(), model, None, 0, None);
+        assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidScoreModel);
+    }
+}
+
+#[tokio::test]
+async fn initializes_metrics_and_kol() {
+    let mut env = TestEnv::new().await;
+    env.initialize_metrics().await;
+
+    let metrics: GameMetrics = env.account(&pda::game_metrics().0).await;
+    assert_eq!(metrics.authority, env.authority());
+    assert_eq!(metrics.fee_vault, pda::fee_vault().0);
+    assert_eq!(metrics.total_games_started, 0);
+
+    // Sessions need the config, so it starts out all zero
+    let config: GameConfig = env.account(&pda::game_config().0).await;
+    assert_eq!((config.streak_bonus_bps, config.max_streak_bonus_bps, config.max_streak_freezes), (0, 0, 0));
+
+    let kol = env.initialize_kol().await;
+    let kol: Kol = env.account(&kol).await;
+    assert_eq!(kol.id, KOL_ID);
+    assert_eq!(kol.name, "Test KOL");
+}
+
+#[tokio::test]
+async fn streak_config_is_set_by_the_authority() {
+    let mut env = TestEnv::new().await;
+    env.initialize_metrics().await;
+    env.set_streak_config(500, 2_000, GAME_FEE, 2).await;
+
+    let config: GameConfig = env.account(&pda::game_config().0).await;
+    assert_eq!(config.streak_bonus_bps, 500);
+    assert_eq!(config.max_streak_bonus_bps, 2_000);
+    assert_eq!(config.streak_freeze_price, GAME_FEE);
+    assert_eq!(config.max_streak_freezes, 2);
+
+    let authority = env.authority();
+    for (bonus, max_bonus, freeze_price, max_freezes) in [
+        (500, MAX_STREAK_BONUS_BPS + 1, 0, 0),
+        (2_001, 2_000, 0, 0),
+        (0, 0, 0, 1),
+    ] {
+        let ix = instructions::set_streak_config(&authority, bonus, max_bonus, freeze_price, max_freezes);
+        assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidStreakConfig);
+    }
+
+    let stranger = Keypair::new();
+    let ix = instructions::set_streak_config(&stranger.pubkey(), 0, 0, 0, 0);
+    assert!(env.send(&[ix], &[&stranger]).await.is_err());
+}
+
+#[tokio::test]
+async fn only_the_authority_withdraws_fees() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+    env.solve_keeping_deposit(&session).await;
+    env.end_session(&session).await.unwrap();
+
+    let fee_vault = pda::fee_vault().0;
+    let stranger = Keypair::new();
+    let ix = instructions::withdraw_fees(&stranger.pubkey(), &stranger.pubkey(), GAME_FEE, None);
+    assert!(env.send(&[ix], &[&stranger]).await.is_err());
+
+    let authority = env.authority();
+    let vault_before = env.lamports(&fee_vault).await;
+    let ix = instructions::withdraw_fees(&authority, &authority, GAME_FEE, None);
+    env.send(&[ix], &[]).await.unwrap();
+    assert_eq!(env.lamports(&fee_vault).await, vault_before - GAME_FEE);
+
+    // Only the treasury's share can go, and the rent-exempt minimum has to stay behind
+    let ix = instructions::withdraw_fees(&authority, &authority, env.lamports(&fee_vault).await, None);
+    assert!(env.send(&[ix], &[]).await.is_err());
+}
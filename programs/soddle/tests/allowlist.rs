@@ -0,0 +1,130 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use soddle::errors::SoddleError;
+use soddle::states::competition::Competition;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle_client::{allowlist, instructions, pda};
+use solana_program_test::BanksClientError;
+use solana_sdk::signature::{Keypair, Signer};
+
+const LEAD_TIME: i64 = 100;
+
+/// A competition starting `LEAD_TIME` seconds from now.
+async fn upcoming_competition(env: &mut TestEnv) -> Pubkey {
+    env.initialize_metrics().await;
+    env.initialize_kol().await;
+    let authority = env.authority();
+    env.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+    let game_state = pda::game_state(&authority).0;
+    let now = env.now().await;
+
+    let ix = instructions::initialize_competition(
+        &authority,
+        &game_state,
+        COMPETITION_ID,
+        now + LEAD_TIME,
+        now + LEAD_TIME + 1_000,
+        ScoringRule::default(),
+        ScoreModel::default(),
+        None,
+        0,
+        None,
+    );
+    env.send(&[ix], &[]).await.unwrap();
+    pda::competition(&game_state, COMPETITION_ID).0
+}
+
+async fn set_root(env: &mut TestEnv, competition: &Pubkey, root: Option<[u8; 32]>) -> Result<(), BanksClientError> {
+    let ix = instructions::set_allowlist_root(&env.authority(), competition, root);
+    env.send(&[ix], &[]).await
+}
+
+fn invited(player: Pubkey) -> Vec<Pubkey> {
+    let mut players: Vec<Pubkey> = (0..4).map(|_| Keypair::new().pubkey()).collect();
+    players.insert(2, player);
+    players
+}
+
+#[test]
+fn proofs_check_out_for_every_player() {
+    let players: Vec<Pubkey> = (0..7).map(|_| Keypair::new().pubkey()).collect();
+    let root = allowlist::root(&players).unwrap();
+
+    for (index, player) in players.iter().enumerate() {
+        assert!(allowlist::verify(&root, player, &allowlist::proof(&players, index)));
+    }
+    assert!(!allowlist::verify(&root, &Keypair::new().pubkey(), &allowlist::proof(&players, 0)));
+    assert!(!allowlist::verify(&root, &players[1], &allowlist::proof(&players, 0)));
+
+    // A single player is their own root
+    assert!(allowlist::verify(&allowlist::leaf(&players[0]), &players[0], &[]));
+    assert_eq!(allowlist::root(&[]), None);
+}
+
+#[tokio::test]
+async fn invited_players_can_open_sessions() {
+    let mut env = TestEnv::new().await;
+    let competition = upcoming_competition(&mut env).await;
+    let players = invited(env.player.pubkey());
+    set_root(&mut env, &competition, allowlist::root(&players)).await.unwrap();
+
+    let competition_account: Competition = env.account(&competition).await;
+    assert_eq!(competition_account.allowlist_root, allowlist::root(&players));
+
+    assert_soddle_error(
+        env.open_invited_session(competition, Vec::new()).await.map(drop),
+        SoddleError::NotOnAllowlist,
+    );
+    // Another player's proof doesn't fit
+    assert_soddle_error(
+        env.open_invited_session(competition, allowlist::proof(&players, 1)).await.map(drop),
+        SoddleError::NotOnAllowlist,
+    );
+
+    let session = env.open_invited_session(competition, allowlist::proof(&players, 2)).await.unwrap();
+    assert!(env.exists(&session.address).await);
+}
+
+#[tokio::test]
+async fn players_left_off_the_list_are_refused() {
+    let mut env = TestEnv::new().await;
+    let competition = upcoming_competition(&mut env).await;
+    let others: Vec<Pubkey> = (0..3).map(|_| Keypair::new().pubkey()).collect();
+    set_root(&mut env, &competition, allowlist::root(&others)).await.unwrap();
+
+    assert_soddle_error(
+        env.open_invited_session(competition, allowlist::proof(&others, 0)).await.map(drop),
+        SoddleError::NotOnAllowlist,
+    );
+
+    // Clearing the root opens the competition again
+    env.warp(1).await;
+    set_root(&mut env, &competition, None).await.unwrap();
+    env.open_session(competition).await;
+}
+
+#[tokio::test]
+async fn the_allowlist_is_fixed_once_the_competition_starts() {
+    let mut env = TestEnv::new().await;
+    let competition = upcoming_competition(&mut env).await;
+    let root = allowlist::root(&invited(env.player.pubkey()));
+    env.warp(LEAD_TIME).await;
+
+    assert_soddle_error(set_root(&mut env, &competition, root).await, SoddleError::CompetitionStarted);
+}
+
+#[tokio::test]
+async fn only_the_competition_authority_sets_the_allowlist() {
+    let mut env = TestEnv::new().await;
+    let competition = upcoming_competition(&mut env).await;
+    let player = env.player.insecure_clone();
+
+    let ix = instructions::set_allowlist_root(&player.pubkey(), &competition, Some([7; 32]));
+    assert!(env.send(&[ix], &[&player]).await.is_err());
+
+    let competition_account: Competition = env.account(&competition).await;
+    assert_eq!(competition_account.allowlist_root, None);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::{Account as TokenAccount, Mint};
+use common::*;
+use soddle::achievements::Achievement;
+use soddle::errors::SoddleError;
+use soddle_client::{instructions, pda};
+use solana_sdk::signature::{Keypair, Signer};
+
+async fn create_badge(env: &mut TestEnv, achievement: Achievement) {
+    let ix = instructions::create_badge_mint(
+        &env.authority(),
+        achievement,
+        format!("{achievement:?}"),
+        "SODDLE".to_string(),
+        "https://example.com/badge.json".to_string(),
+    );
+    env.send(&[ix], &[]).await.unwrap();
+}
+
+async fn badge_balance(env: &mut TestEnv, achievement: Achievement) -> u64 {
+    let address = instructions::badge_account(&env.player.pubkey(), achievement);
+    let account = env.ctx.banks_client.get_account(address).await.unwrap().unwrap();
+    StateWithExtensions::<TokenAccount>::unpack(&account.data).unwrap().base.amount
+}
+
+#[tokio::test]
+async fn badge_mints_are_non_transferable_with_metadata() {
+    let mut env = TestEnv::new().await;
+    env.initialize_metrics().await;
+    create_badge(&mut env, Achievement::FirstSolve).await;
+
+    let address = pda::badge_mint(Achievement::FirstSolve).0;
+    let account = env.ctx.banks_client.get_account(address).await.unwrap().unwrap();
+    let mint = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+    assert_eq!(mint.base.decimals, 0);
+    assert_eq!(mint.base.mint_authority, Some(address).into());
+    let extensions = mint.get_extension_types().unwrap();
+    for extension in [
+        ExtensionType::NonTransferable,
+        ExtensionType::MetadataPointer,
+        ExtensionType::TokenMetadata,
+    ] {
+        assert!(extensions.contains(&extension), "missing {extension:?}");
+    }
+
+    let stranger = Keypair::new();
+    let ix = instructions::create_badge_mint(
+        &stranger.pubkey(),
+        Achievement::PerfectScore,
+        "Perfect".to_string(),
+        "SODDLE".to_string(),
+        String::new(),
+    );
+    assert!(env.send(&[ix], &[&stranger]).await.is_err());
+}
+
+#[tokio::test]
+async fn earned_badges_are_claimed_once() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    create_badge(&mut env, Achievement::FirstSolve).await;
+    create_badge(&mut env, Achievement::SevenDayStreak).await;
+
+    env.start_attempt(&session).await.unwrap();
+    env.guess(&session, ANSWER).await.unwrap();
+    env.end_session(&session).await.unwrap();
+
+    let player = env.player.insecure_clone();
+    let ix = instructions::claim_badge(&player.pubkey(), Achievement::FirstSolve);
+    env.send(&[ix], &[&player]).await.unwrap();
+    assert_eq!(badge_balance(&mut env, Achievement::FirstSolve).await, 1);
+
+    env.warp(1).await;
+    let ix = instructions::claim_badge(&player.pubkey(), Achievement::FirstSolve);
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::BadgeAlreadyClaimed);
+
+    let ix = instructions::claim_badge(&player.pubkey(), Achievement::SevenDayStreak);
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::BadgeNotEarned);
+}
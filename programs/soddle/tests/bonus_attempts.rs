@@ -0,0 +1,120 @@
+mod common;
+
+use common::*;
+use soddle::constants::{GAME_FEE, MAX_DAILY_ATTEMPTS, MAX_SESSION_ATTEMPTS, SECONDS_PER_DAY};
+use soddle::errors::SoddleError;
+use soddle::fees::bonus_attempt_price;
+use soddle::states::{game_metrics::GameMetrics, game_session::GameSession, player_profile::PlayerProfile};
+use soddle_client::{instructions, pda};
+use solana_program_test::BanksClientError;
+use solana_sdk::signature::Signer;
+
+const PRICE: u64 = 2 * GAME_FEE;
+const STEP_BPS: u16 = 5_000;
+
+async fn buy(env: &mut TestEnv, session: &Session) -> Result<(), BanksClientError> {
+    let player = env.player.insecure_clone();
+    let ix = instructions::buy_bonus_attempt(&player.pubkey(), &session.address, session.season_pass);
+    let result = env.send(&[ix], &[&player]).await;
+    env.warp(0).await;
+    result
+}
+
+async fn use_daily_attempts(env: &mut TestEnv, session: &Session) {
+    for _ in 0..MAX_DAILY_ATTEMPTS {
+        env.start_attempt(session).await.unwrap();
+        env.guess(session, ANSWER).await.unwrap();
+        // A fresh slot, so the next identical start isn't deduplicated
+        env.warp(0).await;
+    }
+}
+
+#[test]
+fn bonus_attempts_get_dearer() {
+    assert_eq!(bonus_attempt_price(1_000, STEP_BPS, 0), Some(1_000));
+    assert_eq!(bonus_attempt_price(1_000, STEP_BPS, 1), Some(1_500));
+    assert_eq!(bonus_attempt_price(1_000, STEP_BPS, 2), Some(2_250));
+    assert_eq!(bonus_attempt_price(1_000, 0, 3), Some(1_000));
+    assert_eq!(bonus_attempt_price(u64::MAX, STEP_BPS, 1), None);
+}
+
+#[tokio::test]
+async fn stuck_players_can_buy_another_attempt() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    let ix = instructions::set_bonus_attempt_config(&env.authority(), PRICE, STEP_BPS);
+    env.send(&[ix], &[]).await.unwrap();
+
+    use_daily_attempts(&mut env, &session).await;
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::MaxAttemptsReached);
+
+    let fee_vault = pda::fee_vault().0;
+    let vault_before = env.lamports(&fee_vault).await;
+    buy(&mut env, &session).await.unwrap();
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + PRICE);
+
+    // Already paid for, so the attempt itself is free
+    env.start_attempt(&session).await.unwrap();
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + PRICE);
+
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!((game_session.bonus_attempts, game_session.current_attempt), (1, MAX_DAILY_ATTEMPTS + 1));
+    assert_eq!(game_session.deposit, MAX_DAILY_ATTEMPTS as u64 * GAME_FEE);
+    let flags: Vec<bool> = game_session.played_attempts().iter().map(|attempt| attempt.is_bonus).collect();
+    assert_eq!(flags, [false, false, false, true]);
+
+    let metrics: GameMetrics = env.account(&pda::game_metrics().0).await;
+    assert_eq!(metrics.total_fees_collected, MAX_DAILY_ATTEMPTS as u64 * GAME_FEE + PRICE);
+
+    // The next one costs more
+    let vault_before = env.lamports(&fee_vault).await;
+    buy(&mut env, &session).await.unwrap();
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + PRICE * 3 / 2);
+}
+
+#[tokio::test]
+async fn purchases_stop_when_the_session_is_full() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    let ix = instructions::set_bonus_attempt_config(&env.authority(), PRICE, 0);
+    env.send(&[ix], &[]).await.unwrap();
+
+    for _ in MAX_DAILY_ATTEMPTS..MAX_SESSION_ATTEMPTS {
+        buy(&mut env, &session).await.unwrap();
+    }
+    assert_soddle_error(buy(&mut env, &session).await, SoddleError::BonusAttemptLimitReached);
+}
+
+#[tokio::test]
+async fn bonus_attempts_are_off_until_priced() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    assert_soddle_error(buy(&mut env, &session).await, SoddleError::BonusAttemptsUnavailable);
+}
+
+#[tokio::test]
+async fn the_price_climbs_per_player_and_day() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    let ix = instructions::set_bonus_attempt_config(&env.authority(), PRICE, STEP_BPS);
+    env.send(&[ix], &[]).await.unwrap();
+    let fee_vault = pda::fee_vault().0;
+    buy(&mut env, &first).await.unwrap();
+
+    // A new session the same day doesn't start the price over
+    env.warp(1).await;
+    let second = env.open_session(first.competition).await;
+    let vault_before = env.lamports(&fee_vault).await;
+    buy(&mut env, &second).await.unwrap();
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + PRICE * 3 / 2);
+
+    // The next day does
+    env.warp(SECONDS_PER_DAY).await;
+    let next_day = env.open_session(first.competition).await;
+    let vault_before = env.lamports(&fee_vault).await;
+    buy(&mut env, &next_day).await.unwrap();
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + PRICE);
+
+    let profile: PlayerProfile = env.account(&pda::player_profile(&env.player.pubkey()).0).await;
+    assert_eq!(profile.bonus_attempts_bought, 1);
+}
@@ -0,0 +1,478 @@
+#![allow(dead_code)]
+
+use anchor_lang::prelude::{AccountInfo, Clock, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensions;
+use anchor_spl::token_2022::spl_token_2022::state::{Account as TokenAccount, Mint};
+use soddle::constants::{INITIAL_SCORE, SESSION_DURATION};
+use soddle::errors::SoddleError;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle::states::voucher::{SignedVoucher, Voucher};
+use soddle::states::token_gate::TokenGate;
+use soddle_client::instructions::{AttemptOptions, TokenFee, TokenHolding};
+use soddle_client::{instructions, pda};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const ANSWER: &str = "correct-kol";
+pub const COMPETITION_ID: u64 = 1;
+pub const KOL_ID: u64 = 7;
+
+// anchor's `entry` wants `&'info [AccountInfo<'info>]`. The accounts outlive the
+// instruction in the test runtime, so leaking the slice to widen the lifetime is fine.
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    soddle::entry(program_id, accounts, data)
+}
+
+pub struct TestEnv {
+    pub ctx: ProgramTestContext,
+    pub player: Keypair,
+    pub oracle: Keypair,
+}
+
+/// A session that has been created, has a KOL and a verifier, but no attempt yet.
+pub struct Session {
+    pub address: Pubkey,
+    pub start_time: i64,
+    pub competition: Pubkey,
+    pub fee: Option<TokenFee>,  // Set for competitions that charge in a token
+    pub referrer: Option<Pubkey>,  // Set once the player has registered one
+    pub season_pass: bool,  // Set once the player has bought one
+    pub is_practice: bool,
+    pub holding: Option<TokenHolding>,  // Set for token-gated competitions
+}
+
+impl Session {
+    pub fn attempt_options(&self) -> AttemptOptions {
+        AttemptOptions {
+            referrer: self.referrer,
+            fee: self.fee,
+            season_pass: self.season_pass,
+            holding: self.holding,
+            practice: self.is_practice,
+            ..AttemptOptions::default()
+        }
+    }
+}
+
+impl TestEnv {
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::new("soddle", soddle::ID, processor!(process_instruction));
+        let player = Keypair::new();
+        let oracle = Keypair::new();
+        for funded in [&player, &oracle] {
+            program_test.add_account(
+                funded.pubkey(),
+                Account {
+                    lamports: 10 * LAMPORTS_PER_SOL,
+                    ..Account::default()
+                },
+            );
+        }
+
+        TestEnv {
+            ctx: program_test.start_with_context().await,
+            player,
+            oracle,
+        }
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        self.ctx.payer.pubkey()
+    }
+
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await?;
+        let mut all_signers: Vec<&Keypair> = vec![&self.ctx.payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&self.ctx.payer.pubkey()), &all_signers, blockhash);
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.ctx.banks_client.get_sysvar().await.unwrap()
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    /// Moves the bank to the next slot and sets the clock `seconds` into the future.
+    pub async fn warp(&mut self, seconds: i64) {
+        let before = self.clock().await;
+        self.ctx.warp_to_slot(before.slot + 1).unwrap();
+        let mut clock = self.clock().await;
+        clock.unix_timestamp = before.unix_timestamp + seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.ctx.banks_client.get_account(*address).await.unwrap().unwrap();
+        soddle_client::accounts::decode(&account.data).unwrap()
+    }
+
+    pub async fn exists(&mut self, address: &Pubkey) -> bool {
+        self.ctx.banks_client.get_account(*address).await.unwrap().is_some()
+    }
+
+    pub async fn data_len(&mut self, address: &Pubkey) -> usize {
+        self.ctx.banks_client.get_account(*address).await.unwrap().unwrap().data.len()
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*address).await.unwrap()
+    }
+
+    /// A fresh mint of `token_program` whose mint authority is the payer.
+    pub async fn create_mint(&mut self, decimals: u8, token_program: &Pubkey) -> Pubkey {
+        let mint = Keypair::new();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let payer = self.authority();
+        let ixs = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                token_program,
+            ),
+            spl_token_2022::instruction::initialize_mint2(token_program, &mint.pubkey(), &payer, None, decimals).unwrap(),
+        ];
+        self.send(&ixs, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    /// A token account of `owner` for `mint`, holding `amount` freshly minted tokens.
+    pub async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey, token_program: &Pubkey, amount: u64) -> Pubkey {
+        let account = Keypair::new();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let payer = self.authority();
+        let mut ixs = vec![
+            system_instruction::create_account(
+                &payer,
+                &account.pubkey(),
+                rent.minimum_balance(TokenAccount::LEN),
+                TokenAccount::LEN as u64,
+                token_program,
+            ),
+            spl_token_2022::instruction::initialize_account3(token_program, &account.pubkey(), mint, owner).unwrap(),
+        ];
+        if amount > 0 {
+            ixs.push(
+                spl_token_2022::instruction::mint_to(token_program, mint, &account.pubkey(), &payer, &[], amount).unwrap(),
+            );
+        }
+        self.send(&ixs, &[&account]).await.unwrap();
+        account.pubkey()
+    }
+
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        let account = self.ctx.banks_client.get_account(*address).await.unwrap().unwrap();
+        StateWithExtensions::<TokenAccount>::unpack(&account.data).unwrap().base.amount
+    }
+
+    pub async fn initialize_metrics(&mut self) {
+        let ix = instructions::initialize_metrics(&self.authority());
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    pub async fn initialize_kol(&mut self) -> Pubkey {
+        let ix = instructions::initialize_kol(
+            &self.authority(),
+            instructions::KolInfo {
+                id: KOL_ID,
+                name: "Test KOL".to_string(),
+                age: 25,
+                country: "Nowhere".to_string(),
+                pfp_type: "human".to_string(),
+                pfp: "https://example.com/pfp.jpg".to_string(),
+                account_creation: 2020,
+                followers: 10_000,
+                ecosystem: "Solana".to_string(),
+            },
+        );
+        self.send(&[ix], &[]).await.unwrap();
+        pda::kol(KOL_ID).0
+    }
+
+    pub async fn set_streak_config(
+        &mut self,
+        streak_bonus_bps: u16,
+        max_streak_bonus_bps: u16,
+        streak_freeze_price: u64,
+        max_streak_freezes: u8,
+    ) {
+        let ix = instructions::set_streak_config(
+            &self.authority(),
+            streak_bonus_bps,
+            max_streak_bonus_bps,
+            streak_freeze_price,
+            max_streak_freezes,
+        );
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    /// Creates the payer's game state and a competition running from now for a day.
+    pub async fn initialize_competition(&mut self, scoring_rule: ScoringRule, score_model: ScoreModel) -> Pubkey {
+        self.initialize_competition_with_fee(scoring_rule, score_model, None, 0).await
+    }
+
+    pub async fn initialize_competition_with_fee(
+        &mut self,
+        scoring_rule: ScoringRule,
+        score_model: ScoreModel,
+        fee_mint: Option<Pubkey>,
+        fee_amount: u64,
+    ) -> Pubkey {
+        self.initialize_competition_with(scoring_rule, score_model, fee_mint, fee_amount, None).await
+    }
+
+    pub async fn initialize_gated_competition(&mut self, token_gate: TokenGate) -> Pubkey {
+        self.initialize_competition_with(ScoringRule::default(), ScoreModel::default(), None, 0, Some(token_gate))
+            .await
+    }
+
+    async fn initialize_competition_with(
+        &mut self,
+        scoring_rule: ScoringRule,
+        score_model: ScoreModel,
+        fee_mint: Option<Pubkey>,
+        fee_amount: u64,
+        token_gate: Option<TokenGate>,
+    ) -> Pubkey {
+        let authority = self.authority();
+        self.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+        let game_state = pda::game_state(&authority).0;
+
+        let now = self.now().await;
+        let ix = instructions::initialize_competition(
+            &authority,
+            &game_state,
+            COMPETITION_ID,
+            now,
+            now + SESSION_DURATION,
+            scoring_rule,
+            score_model,
+            fee_mint,
+            fee_amount,
+            token_gate,
+        );
+        self.send(&[ix], &[]).await.unwrap();
+        pda::competition(&game_state, COMPETITION_ID).0
+    }
+
+    /// A competition of the player's own game state under COMPETITION_ID, free and
+    /// open to all, to pass off as the one a session was opened for.
+    pub async fn lookalike_competition(&mut self) -> Pubkey {
+        let player = self.player.insecure_clone();
+        let ix = instructions::initialize_game_state(&player.pubkey());
+        self.send(&[ix], &[&player]).await.unwrap();
+        let game_state = pda::game_state(&player.pubkey()).0;
+
+        let now = self.now().await;
+        let ix = instructions::initialize_competition(
+            &player.pubkey(),
+            &game_state,
+            COMPETITION_ID,
+            now,
+            now + SESSION_DURATION,
+            ScoringRule::FixedPoints { points: INITIAL_SCORE },
+            ScoreModel::default(),
+            None,
+            0,
+            None,
+        );
+        self.send(&[ix], &[&player]).await.unwrap();
+        pda::competition(&game_state, COMPETITION_ID).0
+    }
+
+    /// Metrics, vault, KOL, competition, session and verifier: everything needed
+    /// before `start_game_session`.
+    pub async fn setup_session(&mut self) -> Session {
+        self.setup_session_with(ScoringRule::default(), ScoreModel::default()).await
+    }
+
+    pub async fn setup_session_with(&mut self, scoring_rule: ScoringRule, score_model: ScoreModel) -> Session {
+        self.initialize_metrics().await;
+        self.initialize_kol().await;
+        let competition = self.initialize_competition(scoring_rule, score_model).await;
+        self.open_session(competition).await
+    }
+
+    /// Another session of the player, for a competition and KOL that already exist.
+    pub async fn open_session(&mut self, competition: Pubkey) -> Session {
+        self.open_session_of_type(competition, 1).await
+    }
+
+    pub async fn open_session_of_type(&mut self, competition: Pubkey, game_type: u8) -> Session {
+        self.open_session_with_mode(competition, game_type, false).await
+    }
+
+    /// A free practice session, with its verifier.
+    pub async fn open_practice_session(&mut self, competition: Pubkey) -> Session {
+        self.open_session_with_mode(competition, 1, true).await
+    }
+
+    async fn open_session_with_mode(&mut self, competition: Pubkey, game_type: u8, is_practice: bool) -> Session {
+        self.try_open_session(competition, game_type, is_practice, Vec::new()).await.unwrap()
+    }
+
+    /// Opens a session with `proof` of the player's place on the competition's allowlist.
+    pub async fn open_invited_session(
+        &mut self,
+        competition: Pubkey,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<Session, BanksClientError> {
+        self.try_open_session(competition, 1, false, proof).await
+    }
+
+    async fn try_open_session(
+        &mut self,
+        competition: Pubkey,
+        game_type: u8,
+        is_practice: bool,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<Session, BanksClientError> {
+        let kol = pda::kol(KOL_ID).0;
+        let start_time = self.now().await;
+        let player = self.player.pubkey();
+        let ix = instructions::initialize_game_session(
+            &player,
+            &kol,
+            &competition,
+            COMPETITION_ID,
+            start_time,
+            game_type,
+            is_practice,
+            proof,
+        );
+        self.send(&[ix], &[&self.player.insecure_clone()]).await?;
+
+        let address = pda::game_session(&player, COMPETITION_ID, start_time).0;
+        let ix = instructions::initialize_verifier(
+            &self.oracle.pubkey(),
+            &address,
+            is_practice,
+            hash(ANSWER.as_bytes()).to_bytes(),
+        );
+        self.send(&[ix], &[&self.oracle.insecure_clone()]).await.unwrap();
+
+        Ok(Session {
+            address,
+            start_time,
+            competition,
+            fee: None,
+            referrer: None,
+            season_pass: false,
+            is_practice,
+            holding: None,
+        })
+    }
+
+    pub async fn start_attempt(&mut self, session: &Session) -> Result<(), BanksClientError> {
+        let ix = instructions::start_game_session(
+            &self.player.pubkey(),
+            &pda::kol(KOL_ID).0,
+            &session.competition,
+            COMPETITION_ID,
+            session.start_time,
+            session.attempt_options(),
+        );
+        self.send(&[ix], &[&self.player.insecure_clone()]).await
+    }
+
+    /// Starts an attempt redeeming `voucher`, signed by the metrics authority.
+    pub async fn start_attempt_with_voucher(
+        &mut self,
+        session: &Session,
+        voucher: Voucher,
+    ) -> Result<(), BanksClientError> {
+        let signed = SignedVoucher {
+            voucher,
+            signature: self.ctx.payer.sign_message(&voucher.message()).into(),
+        };
+        let ixs = [
+            instructions::ed25519_verify(&self.authority(), &signed.signature, &voucher.message()),
+            instructions::start_game_session(
+                &self.player.pubkey(),
+                &pda::kol(KOL_ID).0,
+                &session.competition,
+                COMPETITION_ID,
+                session.start_time,
+                AttemptOptions {
+                    voucher: Some(signed),
+                    ..session.attempt_options()
+                },
+            ),
+        ];
+        self.send(&ixs, &[&self.player.insecure_clone()]).await
+    }
+
+    /// Submits `guess` together with the oracle's Ed25519 attestation.
+    pub async fn guess(&mut self, session: &Session, guess: &str) -> Result<(), BanksClientError> {
+        let message = instructions::guess_message(guess, &hash(ANSWER.as_bytes()).to_bytes());
+        let signature: [u8; 64] = self.oracle.sign_message(&message).into();
+        let ixs = [
+            instructions::ed25519_verify(&self.oracle.pubkey(), &signature, &message),
+            instructions::make_guess(
+                &self.player.pubkey(),
+                &self.oracle.pubkey(),
+                &session.address,
+                session.is_practice,
+                &session.competition,
+                guess.to_string(),
+                signature,
+            ),
+        ];
+        let signers = [self.player.insecure_clone(), self.oracle.insecure_clone()];
+        self.send(&ixs, &[&signers[0], &signers[1]]).await
+    }
+
+    /// Solves the current attempt on the sixth guess, too late for a refund.
+    pub async fn solve_keeping_deposit(&mut self, session: &Session) {
+        for n in 0..5 {
+            self.guess(session, &format!("wrong-{n}")).await.unwrap();
+        }
+        self.guess(session, ANSWER).await.unwrap();
+    }
+
+    /// Sent by the payer rather than the player, as anyone may expire a session.
+    pub async fn expire_session(&mut self, session: &Session) -> Result<(), BanksClientError> {
+        let game_session = self.account(&session.address).await;
+        let ix = instructions::expire_game_session(&session.address, &game_session);
+        self.send(&[ix], &[]).await
+    }
+
+    pub async fn end_session(&mut self, session: &Session) -> Result<(), BanksClientError> {
+        let ix = instructions::end_game_session(
+            &self.player.pubkey(),
+            &session.address,
+            &session.competition,
+            &pda::kol(KOL_ID).0,
+            session.referrer,
+            session.fee,
+        );
+        self.send(&[ix], &[&self.player.insecure_clone()]).await
+    }
+}
+
+pub fn assert_soddle_error(result: Result<(), BanksClientError>, expected: SoddleError) {
+    let code: u32 = expected.into();
+    match result.expect_err("transaction should have failed").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+            assert_eq!(actual, code, "expected {expected:?}")
+        }
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
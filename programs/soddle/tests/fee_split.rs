@@ -0,0 +1,186 @@
+mod common;
+
+use common::*;
+use soddle::constants::{GAME_FEE, SESSION_DURATION};
+use soddle::errors::SoddleError;
+use soddle::fees::split_fee;
+use soddle::states::fee_ledger::{FeeLedger, FeeParty};
+use soddle::states::fee_split::FeeSplit;
+use soddle::states::fee_vault::FeeVault;
+use soddle::states::game_config::GameConfig;
+use soddle::states::game_session::GameSession;
+use soddle_client::{instructions, pda};
+use solana_sdk::signature::{Keypair, Signer};
+
+async fn ledgers(env: &mut TestEnv, session: &Session) -> [FeeLedger; 3] {
+    [
+        env.account(&pda::treasury_ledger(None).0).await,
+        env.account(&pda::prize_pool_ledger(&session.competition, None).0).await,
+        env.account(&pda::kol_ledger(&pda::kol(KOL_ID).0, None).0).await,
+    ]
+}
+
+async fn split_session(env: &mut TestEnv) -> Session {
+    let session = env.setup_session().await;
+    let ix = instructions::set_fee_split(&env.authority(), 5_000, 3_000, 1_500, 500);
+    env.send(&[ix], &[]).await.unwrap();
+    env.start_attempt(&session).await.unwrap();
+    session
+}
+
+#[test]
+fn split_adds_up_to_the_fee() {
+    let config = GameConfig {
+        streak_bonus_bps: 0,
+        max_streak_bonus_bps: 0,
+        streak_freeze_price: 0,
+        max_streak_freezes: 0,
+        treasury_share_bps: 3_333,
+        prize_pool_share_bps: 3_333,
+        kol_share_bps: 3_333,
+        referrer_share_bps: 1,
+        season_pass_price: 0,
+        season_pass_duration: 0,
+        season_pass_bonus_attempts: 0,
+        bonus_attempt_price: 0,
+        bonus_attempt_price_step_bps: 0,
+        bump: 0,
+    };
+    let split = split_fee(100, &config, true);
+    assert_eq!(split, FeeSplit { treasury: 34, prize_pool: 33, kol: 33, referrer: 0 });
+
+    // Without a referrer, their share is the treasury's
+    let split = split_fee(GAME_FEE, &config, false);
+    assert_eq!(split.referrer, 0);
+    assert_eq!(split.total(), GAME_FEE);
+
+    let unset = GameConfig { prize_pool_share_bps: 0, kol_share_bps: 0, referrer_share_bps: 0, ..config };
+    assert_eq!(split_fee(GAME_FEE, &unset, true).treasury, GAME_FEE);
+}
+
+#[tokio::test]
+async fn deposits_are_split_into_pending_shares() {
+    let mut env = TestEnv::new().await;
+    let session = split_session(&mut env).await;
+
+    // No referrer yet, so the treasury also takes the referrer's 5%
+    let expected = FeeSplit {
+        treasury: GAME_FEE * 55 / 100,
+        prize_pool: GAME_FEE * 30 / 100,
+        kol: GAME_FEE * 15 / 100,
+        referrer: 0,
+    };
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.fee_split, expected);
+
+    let [treasury, prize_pool, kol] = ledgers(&mut env, &session).await;
+    assert_eq!((treasury.party, treasury.beneficiary), (FeeParty::Treasury, pda::fee_vault().0));
+    assert_eq!((prize_pool.party, prize_pool.beneficiary), (FeeParty::PrizePool, session.competition));
+    assert_eq!((kol.party, kol.beneficiary), (FeeParty::Kol, pda::kol(KOL_ID).0));
+    assert_eq!(
+        [treasury.pending, prize_pool.pending, kol.pending],
+        [expected.treasury, expected.prize_pool, expected.kol]
+    );
+    assert_eq!([treasury.balance, prize_pool.balance, kol.balance], [0, 0, 0]);
+}
+
+#[tokio::test]
+async fn kept_deposits_settle_into_balances() {
+    let mut env = TestEnv::new().await;
+    let session = split_session(&mut env).await;
+    env.solve_keeping_deposit(&session).await;
+    env.end_session(&session).await.unwrap();
+
+    let [treasury, prize_pool, kol] = ledgers(&mut env, &session).await;
+    assert_eq!([treasury.pending, prize_pool.pending, kol.pending], [0, 0, 0]);
+    assert_eq!(
+        [treasury.balance, prize_pool.balance, kol.balance],
+        [GAME_FEE * 55 / 100, GAME_FEE * 30 / 100, GAME_FEE * 15 / 100]
+    );
+
+    // The treasury cannot reach into the other ledgers
+    let authority = env.authority();
+    let ix = instructions::withdraw_fees(&authority, &authority, treasury.balance + 1, None);
+    assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InsufficientFunds);
+    let ix = instructions::withdraw_fees(&authority, &authority, treasury.balance, None);
+    env.send(&[ix], &[]).await.unwrap();
+    let [treasury, prize_pool, _] = ledgers(&mut env, &session).await;
+    assert_eq!((treasury.balance, treasury.total_paid), (0, GAME_FEE * 55 / 100));
+    assert_eq!(prize_pool.balance, GAME_FEE * 30 / 100);
+}
+
+#[tokio::test]
+async fn refunded_deposits_leave_nothing_behind() {
+    let mut env = TestEnv::new().await;
+    let session = split_session(&mut env).await;
+    env.guess(&session, ANSWER).await.unwrap();
+    env.end_session(&session).await.unwrap();
+
+    for ledger in ledgers(&mut env, &session).await {
+        assert_eq!((ledger.pending, ledger.balance, ledger.total_earned), (0, 0, 0));
+    }
+}
+
+#[tokio::test]
+async fn expired_sessions_release_their_shares() {
+    let mut env = TestEnv::new().await;
+    let session = split_session(&mut env).await;
+    env.guess(&session, "wrong-kol").await.unwrap();
+
+    assert_soddle_error(env.expire_session(&session).await, SoddleError::SessionNotExpired);
+
+    // Never ended, so the deposit can no longer be refunded and the shares are earned
+    env.warp(SESSION_DURATION).await;
+    let player = env.player.pubkey();
+    let player_before = env.lamports(&player).await;
+    let session_rent = env.lamports(&session.address).await;
+    env.expire_session(&session).await.unwrap();
+
+    assert!(!env.exists(&session.address).await);
+    assert_eq!(env.lamports(&player).await, player_before + session_rent);
+    let [treasury, prize_pool, kol] = ledgers(&mut env, &session).await;
+    assert_eq!([treasury.pending, prize_pool.pending, kol.pending], [0, 0, 0]);
+    assert_eq!(
+        [treasury.balance, prize_pool.balance, kol.balance],
+        [GAME_FEE * 55 / 100, GAME_FEE * 30 / 100, GAME_FEE * 15 / 100]
+    );
+}
+
+#[tokio::test]
+async fn withdrawals_come_out_of_the_collected_total() {
+    let mut env = TestEnv::new().await;
+    let session = split_session(&mut env).await;
+    env.solve_keeping_deposit(&session).await;
+    env.end_session(&session).await.unwrap();
+    let vault: FeeVault = env.account(&pda::fee_vault().0).await;
+    assert_eq!(vault.total_collected, GAME_FEE);
+
+    let authority = env.authority();
+    let ix = instructions::withdraw_fees(&authority, &authority, GAME_FEE * 55 / 100, None);
+    env.send(&[ix], &[]).await.unwrap();
+
+    let vault: FeeVault = env.account(&pda::fee_vault().0).await;
+    assert_eq!(vault.total_collected, GAME_FEE * 45 / 100);
+}
+
+#[tokio::test]
+async fn fee_split_is_validated_and_admin_only() {
+    let mut env = TestEnv::new().await;
+    env.initialize_metrics().await;
+    let authority = env.authority();
+
+    let ix = instructions::set_fee_split(&authority, 5_000, 3_000, 1_500, 499);
+    assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidFeeSplit);
+
+    let stranger = Keypair::new();
+    let ix = instructions::set_fee_split(&stranger.pubkey(), 10_000, 0, 0, 0);
+    assert!(env.send(&[ix], &[&stranger]).await.is_err());
+
+    let ix = instructions::set_fee_split(&authority, 7_000, 2_000, 1_000, 0);
+    env.send(&[ix], &[]).await.unwrap();
+    let config: GameConfig = env.account(&pda::game_config().0).await;
+    assert_eq!(
+        [config.treasury_share_bps, config.prize_pool_share_bps, config.kol_share_bps, config.referrer_share_bps],
+        [7_000, 2_000, 1_000, 0]
+    );
+}
@@ -0,0 +1,319 @@
+mod common;
+
+use anchor_lang::solana_program::hash::hash;
+use common::*;
+use soddle::constants::*;
+use soddle::errors::SoddleError;
+use soddle::states::score_model::{ScoreModel, WrongGuessPenalty};
+use soddle::states::scoring_rule::ScoringRule;
+use soddle::states::{game_metrics::GameMetrics, game_session::GameSession};
+use soddle_client::{instructions, pda};
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn rejects_unknown_game_type() {
+    let mut env = TestEnv::new().await;
+    env.initialize_metrics().await;
+    let kol = env.initialize_kol().await;
+    let competition = env.initialize_competition(ScoringRule::default(), ScoreModel::default()).await;
+    let now = env.now().await;
+    let player = env.player.insecure_clone();
+
+    let ix = instructions::initialize_game_session(
+        &player.pubkey(),
+        &kol,
+        &competition,
+        COMPETITION_ID,
+        now,
+        GAME_TYPE_COUNT + 1,
+        false,
+        Vec::new(),
+    );
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::InvalidGameType);
+}
+
+#[tokio::test]
+async fn starting_an_attempt_charges_the_fee() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    let fee_vault = pda::fee_vault().0;
+    let vault_before = env.lamports(&fee_vault).await;
+
+    env.start_attempt(&session).await.unwrap();
+
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + GAME_FEE);
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.current_attempt, 1);
+    assert_eq!(game_session.deposit, GAME_FEE);
+    assert_eq!(game_session.attempts[0].score, INITIAL_SCORE);
+
+    let metrics: GameMetrics = env.account(&pda::game_metrics().0).await;
+    assert_eq!(metrics.total_games_started, 1);
+    assert_eq!(metrics.total_fees_collected, GAME_FEE);
+}
+
+#[tokio::test]
+async fn daily_attempts_are_capped() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+
+    for _ in 0..MAX_DAILY_ATTEMPTS {
+        env.start_attempt(&session).await.unwrap();
+        env.guess(&session, ANSWER).await.unwrap();
+        // A fresh slot, so the next identical start isn't deduplicated; attempts can't be
+        // started after the session's `start_time`, so the clock stays put
+        env.warp(0).await;
+    }
+
+    assert!(env.start_attempt(&session).await.is_err());
+}
+
+#[tokio::test]
+async fn attempts_start_no_later_than_the_session_start_time() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.warp(1).await;
+
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::InvalidStartTime);
+}
+
+#[tokio::test]
+async fn time_penalty_accrues_per_interval() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+
+    // Two full intervals and a bit
+    env.warp(2 * TIME_PENALTY_INTERVAL + 2).await;
+    env.guess(&session, "wrong-kol").await.unwrap();
+
+    let game_session: GameSession = env.account(&session.address).await;
+    let attempt = &game_session.attempts[0];
+    assert_eq!(attempt.guesses, 1);
+    assert!(!attempt.completed);
+    assert_eq!(attempt.score, INITIAL_SCORE - 2 * TIME_PENALTY - WRONG_GUESS_PENALTY);
+}
+
+#[tokio::test]
+async fn attempts_record_every_guess() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+    let started = env.now().await;
+
+    env.guess(&session, "wrong-kol").await.unwrap();
+    env.warp(TIME_PENALTY_INTERVAL).await;
+    env.guess(&session, ANSWER).await.unwrap();
+
+    let game_session: GameSession = env.account(&session.address).await;
+    let history = &game_session.attempts[0].history;
+    assert_eq!(history.len(), 2);
+
+    assert_eq!(history[0].guess_hash, hash("wrong-kol".as_bytes()).to_bytes());
+    assert_eq!(history[0].timestamp, started);
+    assert_eq!(history[0].score_delta, -(WRONG_GUESS_PENALTY as i32));
+    assert!(!history[0].correct);
+
+    // The flat penalty only applies to the latest guess, so solving wins it back
+    assert_eq!(history[1].timestamp, started + TIME_PENALTY_INTERVAL);
+    assert_eq!(history[1].score_delta, (WRONG_GUESS_PENALTY - TIME_PENALTY) as i32);
+    assert!(history[1].correct);
+
+    let total: i32 = history.iter().map(|record| record.score_delta).sum();
+    assert_eq!(INITIAL_SCORE as i32 + total, game_session.attempts[0].score as i32);
+}
+
+#[tokio::test]
+async fn sessions_grow_with_each_guess() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    assert_eq!(env.data_len(&session.address).await, GameSession::INITIAL_SPACE);
+
+    env.start_attempt(&session).await.unwrap();
+    env.guess(&session, "wrong-kol").await.unwrap();
+    env.guess(&session, ANSWER).await.unwrap();
+
+    assert_eq!(env.data_len(&session.address).await, GameSession::INITIAL_SPACE + 2 * GameSession::GUESS_SPACE);
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.attempts[0].history.len(), 2);
+}
+
+#[tokio::test]
+async fn cumulative_competitions_charge_every_wrong_guess() {
+    let mut env = TestEnv::new().await;
+    let session = env
+        .setup_session_with(
+            ScoringRule::default(),
+            ScoreModel {
+                wrong_guess_penalty: WrongGuessPenalty::Cumulative,
+                ..ScoreModel::default()
+            },
+        )
+        .await;
+    env.start_attempt(&session).await.unwrap();
+
+    for n in 0..3 {
+        env.guess(&session, &format!("wrong-{n}")).await.unwrap();
+    }
+    env.guess(&session, ANSWER).await.unwrap();
+
+    let game_session: GameSession = env.account(&session.address).await;
+    assert!(game_session.attempts[0].completed);
+    assert_eq!(game_session.attempts[0].score, INITIAL_SCORE - 3 * WRONG_GUESS_PENALTY);
+}
+
+#[tokio::test]
+async fn fixed_point_competitions_ignore_time_and_misses() {
+    let mut env = TestEnv::new().await;
+    let session = env
+        .setup_session_with(ScoringRule::FixedPoints { points: 300 }, ScoreModel::default())
+        .await;
+    env.start_attempt(&session).await.unwrap();
+
+    env.warp(10 * TIME_PENALTY_INTERVAL).await;
+    env.guess(&session, "wrong-kol").await.unwrap();
+    env.guess(&session, ANSWER).await.unwrap();
+
+    let game_session: GameSession = env.account(&session.address).await;
+    assert!(game_session.attempts[0].completed);
+    assert_eq!(game_session.attempts[0].score, 300);
+}
+
+#[tokio::test]
+async fn guesses_are_rejected_after_the_attempt_time_limit() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+
+    env.warp(ATTEMPT_TIME_LIMIT - 1).await;
+    env.guess(&session, "wrong-1").await.unwrap();
+
+    env.warp(1).await;
+    assert!(env.guess(&session, "wrong-2").await.is_err());
+}
+
+#[tokio::test]
+async fn attempt_completes_after_max_guesses() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+
+    for n in 0..MAX_GUESSES {
+        env.guess(&session, &format!("wrong-{n}")).await.unwrap();
+    }
+
+    let game_session: GameSession = env.account(&session.address).await;
+    assert!(game_session.attempts[0].completed);
+    assert_eq!(game_session.attempts[0].guesses, MAX_GUESSES);
+    assert!(env.guess(&session, ANSWER).await.is_err());
+}
+
+#[tokio::test]
+async fn guesses_need_the_oracle_attestation() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+
+    let player = env.player.insecure_clone();
+    let oracle = env.oracle.insecure_clone();
+    let ix = instructions::make_guess(
+        &player.pubkey(),
+        &oracle.pubkey(),
+        &session.address,
+        false,
+        &session.competition,
+        ANSWER.to_string(),
+        [0; 64],
+    );
+    assert!(env.send(&[ix], &[&player, &oracle]).await.is_err());
+}
+
+#[tokio::test]
+async fn expired_sessions_are_locked() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+
+    env.warp(24 * 60 * 60).await;
+
+    assert_soddle_error(env.guess(&session, ANSWER).await, SoddleError::SessionExpired);
+    assert_soddle_error(env.end_session(&session).await, SoddleError::SessionExpired);
+}
+
+#[tokio::test]
+async fn quick_solve_refunds_the_deposit() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+    env.guess(&session, ANSWER).await.unwrap();
+
+    let game_session: GameSession = env.account(&session.address).await;
+    assert!(game_session.attempts[0].completed);
+    assert_eq!(game_session.attempts[0].score, INITIAL_SCORE);
+
+    let player = env.player.pubkey();
+    let fee_vault = pda::fee_vault().0;
+    let session_rent = env.lamports(&session.address).await;
+    let player_before = env.lamports(&player).await;
+    let vault_before = env.lamports(&fee_vault).await;
+
+    env.end_session(&session).await.unwrap();
+
+    // The player also pays for the profile created with their first finished session
+    let profile_rent = env.lamports(&pda::player_profile(&player).0).await;
+    assert!(!env.exists(&session.address).await);
+    assert_eq!(env.lamports(&fee_vault).await, vault_before - GAME_FEE);
+    assert_eq!(env.lamports(&player).await, player_before + GAME_FEE + session_rent - profile_rent);
+}
+
+#[tokio::test]
+async fn slow_solve_keeps_the_deposit() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+    for n in 0..5 {
+        env.guess(&session, &format!("wrong-{n}")).await.unwrap();
+    }
+    env.guess(&session, ANSWER).await.unwrap();
+
+    let fee_vault = pda::fee_vault().0;
+    let vault_before = env.lamports(&fee_vault).await;
+    env.end_session(&session).await.unwrap();
+
+    assert!(!env.exists(&session.address).await);
+    assert_eq!(env.lamports(&fee_vault).await, vault_before);
+}
+
+#[tokio::test]
+async fn session_cannot_end_mid_attempt() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+    env.guess(&session, "wrong-kol").await.unwrap();
+
+    assert!(env.end_session(&session).await.is_err());
+}
+
+#[tokio::test]
+async fn sessions_are_bound_to_their_competition() {
+    let mut env = TestEnv::new().await;
+    let mut session = env.setup_session().await;
+    let competition = session.competition;
+    let lookalike = env.lookalike_competition().await;
+
+    session.competition = lookalike;
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::InvalidCompetition);
+
+    session.competition = competition;
+    env.start_attempt(&session).await.unwrap();
+    session.competition = lookalike;
+    assert_soddle_error(env.guess(&session, ANSWER).await, SoddleError::InvalidCompetition);
+
+    session.competition = competition;
+    env.guess(&session, ANSWER).await.unwrap();
+    session.competition = lookalike;
+    assert_soddle_error(env.end_session(&session).await, SoddleError::InvalidCompetition);
+
+    session.competition = competition;
+    env.end_session(&session).await.unwrap();
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::*;
+use soddle::constants::GAME_FEE;
+use soddle::errors::SoddleError;
+use soddle::states::fee_ledger::FeeLedger;
+use soddle::states::kol::Kol;
+use soddle_client::{instructions, pda};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+const KOL_SHARE: u64 = GAME_FEE * 20 / 100;
+
+/// A finished session that kept its deposit, with 20% of fees going to the KOL.
+async fn kept_session(env: &mut TestEnv) {
+    let session = env.setup_session().await;
+    let ix = instructions::set_fee_split(&env.authority(), 8_000, 0, 2_000, 0);
+    env.send(&[ix], &[]).await.unwrap();
+    env.start_attempt(&session).await.unwrap();
+    env.solve_keeping_deposit(&session).await;
+    env.end_session(&session).await.unwrap();
+}
+
+/// A wallet with enough lamports to be rent exempt, so it can receive small payouts.
+async fn funded_wallet(env: &mut TestEnv) -> Keypair {
+    let wallet = Keypair::new();
+    let ix = system_instruction::transfer(&env.authority(), &wallet.pubkey(), LAMPORTS_PER_SOL);
+    env.send(&[ix], &[]).await.unwrap();
+    wallet
+}
+
+#[tokio::test]
+async fn payout_wallet_claims_the_kol_share() {
+    let mut env = TestEnv::new().await;
+    kept_session(&mut env).await;
+    let wallet = funded_wallet(&mut env).await;
+
+    // Accrued before any wallet was set
+    let ledger: FeeLedger = env.account(&pda::kol_ledger(&pda::kol(KOL_ID).0, None).0).await;
+    assert_eq!(ledger.balance, KOL_SHARE);
+
+    let ix = instructions::set_kol_payout_wallet(&env.authority(), KOL_ID, Some(wallet.pubkey()));
+    env.send(&[ix], &[]).await.unwrap();
+    let kol: Kol = env.account(&pda::kol(KOL_ID).0).await;
+    assert_eq!(kol.payout_wallet, Some(wallet.pubkey()));
+
+    let before = env.lamports(&wallet.pubkey()).await;
+    let ix = instructions::claim_kol_rewards(&wallet.pubkey(), KOL_ID, None);
+    env.send(&[ix], &[&wallet]).await.unwrap();
+    assert_eq!(env.lamports(&wallet.pubkey()).await, before + KOL_SHARE);
+
+    let ledger: FeeLedger = env.account(&pda::kol_ledger(&pda::kol(KOL_ID).0, None).0).await;
+    assert_eq!((ledger.balance, ledger.total_paid), (0, KOL_SHARE));
+
+    env.warp(1).await;
+    let ix = instructions::claim_kol_rewards(&wallet.pubkey(), KOL_ID, None);
+    assert_soddle_error(env.send(&[ix], &[&wallet]).await, SoddleError::NoRewardsToClaim);
+}
+
+#[tokio::test]
+async fn only_the_payout_wallet_claims() {
+    let mut env = TestEnv::new().await;
+    kept_session(&mut env).await;
+    let stranger = funded_wallet(&mut env).await;
+
+    // No wallet set yet
+    let ix = instructions::claim_kol_rewards(&stranger.pubkey(), KOL_ID, None);
+    assert_soddle_error(env.send(&[ix], &[&stranger]).await, SoddleError::InvalidPayoutWallet);
+
+    let wallet = Keypair::new();
+    let ix = instructions::set_kol_payout_wallet(&env.authority(), KOL_ID, Some(wallet.pubkey()));
+    env.send(&[ix], &[]).await.unwrap();
+    env.warp(1).await;
+    let ix = instructions::claim_kol_rewards(&stranger.pubkey(), KOL_ID, None);
+    assert_soddle_error(env.send(&[ix], &[&stranger]).await, SoddleError::InvalidPayoutWallet);
+
+    // Nor can anyone but the authority pick the wallet
+    let ix = instructions::set_kol_payout_wallet(&stranger.pubkey(), KOL_ID, Some(stranger.pubkey()));
+    assert!(env.send(&[ix], &[&stranger]).await.is_err());
+}
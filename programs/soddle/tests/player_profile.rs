@@ -0,0 +1,287 @@
+mod common;
+
+use common::*;
+use soddle::constants::*;
+use soddle::states::game_metrics::GameMetrics;
+use soddle::states::game_session::GameSession;
+use soddle::states::player_profile::PlayerProfile;
+use soddle::scoring::day_index;
+use soddle::achievements::Achievement;
+use soddle::errors::SoddleError;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle_client::{instructions, pda};
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn first_session_creates_the_profile() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+    for n in 0..2 {
+        env.guess(&session, &format!("wrong-{n}")).await.unwrap();
+    }
+    env.guess(&session, ANSWER).await.unwrap();
+    env.end_session(&session).await.unwrap();
+
+    let profile: PlayerProfile = env.account(&pda::player_profile(&env.player.pubkey()).0).await;
+    assert_eq!(profile.player, env.player.pubkey());
+    assert_eq!(profile.games_played, 1);
+    assert_eq!(profile.wins, 1);
+    assert_eq!(profile.average_guesses(), Some(3));
+    assert_eq!(profile.best_score, INITIAL_SCORE);
+    assert_eq!(profile.total_fees_paid, GAME_FEE);
+    assert_eq!(profile.total_refunds, GAME_FEE);
+    assert_eq!(profile.guess_distribution[2], 1);
+    assert_eq!(profile.guess_distribution.iter().sum::<u32>(), 1);
+}
+
+#[tokio::test]
+async fn unsolved_sessions_count_as_played_only() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+    for n in 0..MAX_GUESSES {
+        env.guess(&session, &format!("wrong-{n}")).await.unwrap();
+    }
+    env.end_session(&session).await.unwrap();
+
+    let profile: PlayerProfile = env.account(&pda::player_profile(&env.player.pubkey()).0).await;
+    assert_eq!(profile.games_played, 1);
+    assert_eq!(profile.wins, 0);
+    assert_eq!(profile.average_guesses(), None);
+    assert_eq!(profile.total_fees_paid, GAME_FEE);
+    assert_eq!(profile.total_refunds, 0);
+    assert_eq!(profile.guess_distribution, [0; MAX_GUESSES as usize]);
+}
+
+/// Plays a session to its end: solved on the first guess, or failed with every guess wrong.
+async fn play(env: &mut TestEnv, session: &Session, solve: bool) {
+    env.start_attempt(session).await.unwrap();
+    if solve {
+        env.guess(session, ANSWER).await.unwrap();
+    } else {
+        for n in 0..MAX_GUESSES {
+            env.guess(session, &format!("wrong-{n}")).await.unwrap();
+        }
+    }
+    env.end_session(session).await.unwrap();
+}
+
+async fn profile(env: &mut TestEnv) -> PlayerProfile {
+    let address = pda::player_profile(&env.player.pubkey()).0;
+    env.account(&address).await
+}
+
+#[tokio::test]
+async fn consecutive_daily_wins_build_a_streak() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    let first_day = day_index(env.now().await);
+    play(&mut env, &first, true).await;
+
+    // A second win on the same day does not extend it
+    env.warp(60).await;
+    let again = env.open_session(first.competition).await;
+    play(&mut env, &again, true).await;
+    assert_eq!(profile(&mut env).await.current_streak, 1);
+
+    env.warp(SECONDS_PER_DAY).await;
+    let next = env.open_session(first.competition).await;
+    play(&mut env, &next, true).await;
+
+    let profile = profile(&mut env).await;
+    assert_eq!(profile.current_streak, 2);
+    assert_eq!(profile.longest_streak, 2);
+    assert_eq!(profile.last_win_day, first_day + 1);
+    assert_eq!(profile.last_played_day, first_day + 1);
+}
+
+#[tokio::test]
+async fn the_streak_bonus_is_part_of_the_final_score() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    env.set_streak_config(1_000, 1_000, 0, 0).await;  // 10% from the second day on
+    play(&mut env, &first, true).await;
+
+    env.warp(SECONDS_PER_DAY).await;
+    let next = env.open_session(first.competition).await;
+    play(&mut env, &next, true).await;
+
+    let final_score = INITIAL_SCORE + INITIAL_SCORE / 10;
+    let metrics: GameMetrics = env.account(&pda::game_metrics().0).await;
+    assert_eq!((metrics.highest_score, metrics.highest_scorer), (final_score, env.player.pubkey()));
+    assert_eq!(profile(&mut env).await.best_score, final_score);
+
+    // A slow solve is not refunded, even with the bonus lifting its final score past the threshold
+    env.warp(60).await;
+    let slow = env.open_session(first.competition).await;
+    env.start_attempt(&slow).await.unwrap();
+    env.warp(225).await;
+    env.guess(&slow, ANSWER).await.unwrap();
+    let game_session: GameSession = env.account(&slow.address).await;
+    assert!(game_session.best_score() < 800 && game_session.final_score(1_000) >= 800);
+    env.end_session(&slow).await.unwrap();
+    assert_eq!(profile(&mut env).await.total_refunds, 2 * GAME_FEE);
+}
+
+#[tokio::test]
+async fn failing_every_attempt_breaks_the_streak() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    play(&mut env, &first, true).await;
+
+    env.warp(SECONDS_PER_DAY).await;
+    let failed = env.open_session(first.competition).await;
+    play(&mut env, &failed, false).await;
+
+    let profile = profile(&mut env).await;
+    assert_eq!(profile.current_streak, 0);
+    assert_eq!(profile.longest_streak, 1);
+}
+
+#[tokio::test]
+async fn a_missed_day_restarts_the_streak() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    play(&mut env, &first, true).await;
+
+    env.warp(2 * SECONDS_PER_DAY).await;
+    let later = env.open_session(first.competition).await;
+    let later_day = day_index(env.now().await);
+    assert_eq!(profile(&mut env).await.streak_on(later_day), 0);
+    play(&mut env, &later, true).await;
+
+    let profile = profile(&mut env).await;
+    assert_eq!(profile.current_streak, 1);
+    assert_eq!(profile.last_win_day, later_day);
+}
+
+#[tokio::test]
+async fn streak_freezes_cover_missed_days() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    env.set_streak_config(0, 0, GAME_FEE, 2).await;
+    play(&mut env, &first, true).await;
+
+    let player = env.player.insecure_clone();
+    let fee_vault = pda::fee_vault().0;
+    let vault_before = env.lamports(&fee_vault).await;
+    // Identical transactions need a fresh blockhash, hence the warps
+    for _ in 0..2 {
+        let ix = instructions::buy_streak_freeze(&player.pubkey());
+        env.send(&[ix], &[&player]).await.unwrap();
+        env.warp(1).await;
+    }
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + 2 * GAME_FEE);
+    let ix = instructions::buy_streak_freeze(&player.pubkey());
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::StreakFreezeLimitReached);
+
+    // Skips one day, the freeze keeps the streak alive and is used up
+    env.warp(2 * SECONDS_PER_DAY - 2).await;
+    let later = env.open_session(first.competition).await;
+    play(&mut env, &later, true).await;
+
+    let profile = profile(&mut env).await;
+    assert_eq!(profile.current_streak, 2);
+    assert_eq!(profile.streak_freezes, 1);
+}
+
+#[tokio::test]
+async fn streak_freezes_do_not_cover_failed_days() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    env.set_streak_config(0, 0, GAME_FEE, 1).await;
+    play(&mut env, &first, true).await;
+    let player = env.player.insecure_clone();
+    env.send(&[instructions::buy_streak_freeze(&player.pubkey())], &[&player]).await.unwrap();
+
+    env.warp(SECONDS_PER_DAY).await;
+    let failed = env.open_session(first.competition).await;
+    play(&mut env, &failed, false).await;
+
+    let profile = profile(&mut env).await;
+    assert_eq!(profile.current_streak, 0);
+    assert_eq!(profile.streak_freezes, 1);
+}
+
+#[tokio::test]
+async fn instant_solves_unlock_achievements() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    play(&mut env, &session, true).await;
+
+    let profile = profile(&mut env).await;
+    let expected = Achievement::FirstSolve.bit() | Achievement::OneGuessSolve.bit() | Achievement::PerfectScore.bit();
+    assert_eq!(profile.achievements, expected);
+    assert_eq!(profile.game_types_played, 0b001);
+}
+
+#[tokio::test]
+async fn a_speed_bonus_does_not_make_a_second_guess_perfect() {
+    let mut env = TestEnv::new().await;
+    let model = ScoreModel { speed_bonus: 200, speed_bonus_window: ATTEMPT_TIME_LIMIT, ..ScoreModel::default() };
+    let session = env.setup_session_with(ScoringRule::default(), model).await;
+    env.start_attempt(&session).await.unwrap();
+    env.guess(&session, "wrong").await.unwrap();
+    env.guess(&session, ANSWER).await.unwrap();
+    env.end_session(&session).await.unwrap();
+
+    let profile = profile(&mut env).await;
+    assert!(profile.best_score > INITIAL_SCORE);
+    assert_eq!(profile.achievements, Achievement::FirstSolve.bit());
+}
+
+#[tokio::test]
+async fn a_speed_bonus_does_not_make_a_slow_first_guess_perfect() {
+    let mut env = TestEnv::new().await;
+    let model = ScoreModel { speed_bonus: 200, speed_bonus_window: ATTEMPT_TIME_LIMIT, ..ScoreModel::default() };
+    let session = env.setup_session_with(ScoringRule::default(), model).await;
+    env.start_attempt(&session).await.unwrap();
+    env.warp(6 * TIME_PENALTY_INTERVAL).await;
+    env.guess(&session, ANSWER).await.unwrap();
+
+    // The bonus outweighs the time deduction, but points were still lost to the clock
+    let game_session: GameSession = env.account(&session.address).await;
+    assert!(game_session.attempts[0].history[0].score_delta > 0);
+    env.end_session(&session).await.unwrap();
+
+    let profile = profile(&mut env).await;
+    assert_eq!(profile.achievements, Achievement::FirstSolve.bit() | Achievement::OneGuessSolve.bit());
+}
+
+#[tokio::test]
+async fn playing_every_game_type_unlocks_an_achievement() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    play(&mut env, &first, false).await;
+
+    for game_type in 2..=GAME_TYPE_COUNT {
+        env.warp(60).await;
+        let session = env.open_session_of_type(first.competition, game_type).await;
+        assert_eq!(profile(&mut env).await.achievements & Achievement::AllGameTypes.bit(), 0);
+        play(&mut env, &session, false).await;
+    }
+
+    let profile = profile(&mut env).await;
+    assert_eq!(profile.game_types_played, 0b111);
+    assert_eq!(profile.achievements, Achievement::AllGameTypes.bit());
+}
+
+#[tokio::test]
+async fn a_week_long_streak_unlocks_an_achievement() {
+    let mut env = TestEnv::new().await;
+    let first = env.setup_session().await;
+    play(&mut env, &first, true).await;
+
+    for _ in 1..7 {
+        assert_eq!(profile(&mut env).await.achievements & Achievement::SevenDayStreak.bit(), 0);
+        env.warp(SECONDS_PER_DAY).await;
+        let session = env.open_session(first.competition).await;
+        play(&mut env, &session, true).await;
+    }
+
+    let profile = profile(&mut env).await;
+    assert_eq!(profile.current_streak, 7);
+    assert_ne!(profile.achievements & Achievement::SevenDayStreak.bit(), 0);
+}
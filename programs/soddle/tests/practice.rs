@@ -0,0 +1,102 @@
+mod common;
+
+use common::*;
+use soddle::constants::GAME_FEE;
+use soddle::errors::SoddleError;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle::states::{game_metrics::GameMetrics, game_session::GameSession};
+use soddle_client::{instructions, pda};
+use solana_sdk::signature::Signer;
+use solana_sdk::system_instruction;
+
+async fn setup_practice_session(env: &mut TestEnv) -> Session {
+    env.initialize_metrics().await;
+    env.initialize_kol().await;
+    let competition = env.initialize_competition(ScoringRule::default(), ScoreModel::default()).await;
+    env.open_practice_session(competition).await
+}
+
+#[tokio::test]
+async fn practice_is_free_and_left_out_of_metrics() {
+    let mut env = TestEnv::new().await;
+    let session = setup_practice_session(&mut env).await;
+    let fee_vault = pda::fee_vault().0;
+    let vault_before = env.lamports(&fee_vault).await;
+
+    // Answers are checked against the practice verifier
+    assert!(env.exists(&pda::practice_verifier(&session.address).0).await);
+    assert!(!env.exists(&pda::guess_verifier(&session.address).0).await);
+
+    env.start_attempt(&session).await.unwrap();
+    env.guess(&session, ANSWER).await.unwrap();
+
+    assert_eq!(env.lamports(&fee_vault).await, vault_before);
+    let game_session: GameSession = env.account(&session.address).await;
+    assert!(game_session.is_practice);
+    assert_eq!(game_session.deposit, 0);
+    assert!(game_session.attempts[0].completed);
+
+    let metrics: GameMetrics = env.account(&pda::game_metrics().0).await;
+    assert_eq!((metrics.total_games_started, metrics.total_fees_collected), (0, 0));
+
+    // No fee, so no ledgers to create
+    assert!(!env.exists(&pda::treasury_ledger(None).0).await);
+    assert!(!env.exists(&pda::kol_ledger(&pda::kol(KOL_ID).0, None).0).await);
+}
+
+#[tokio::test]
+async fn practice_needs_no_funds_for_the_fee() {
+    let mut env = TestEnv::new().await;
+    let session = setup_practice_session(&mut env).await;
+
+    // Leave the player less than GAME_FEE
+    let player = env.player.insecure_clone();
+    let balance = env.lamports(&player.pubkey()).await;
+    let ix = system_instruction::transfer(&player.pubkey(), &env.authority(), balance - GAME_FEE / 2);
+    env.send(&[ix], &[&player]).await.unwrap();
+
+    env.start_attempt(&session).await.unwrap();
+    env.guess(&session, ANSWER).await.unwrap();
+}
+
+#[tokio::test]
+async fn practice_sessions_are_closed_not_ended() {
+    let mut env = TestEnv::new().await;
+    let session = setup_practice_session(&mut env).await;
+    env.start_attempt(&session).await.unwrap();
+
+    assert_soddle_error(env.end_session(&session).await, SoddleError::PracticeSession);
+
+    // Mid-attempt is fine, and the rent goes back to the player
+    let player = env.player.insecure_clone();
+    let rent = env.lamports(&session.address).await;
+    let before = env.lamports(&player.pubkey()).await;
+    let ix = instructions::close_practice_session(&player.pubkey(), &session.address);
+    env.send(&[ix], &[&player]).await.unwrap();
+
+    assert!(!env.exists(&session.address).await);
+    assert_eq!(env.lamports(&player.pubkey()).await, before + rent);
+    assert!(!env.exists(&pda::player_profile(&player.pubkey()).0).await);
+}
+
+#[tokio::test]
+async fn paid_sessions_cannot_be_closed_as_practice() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    env.start_attempt(&session).await.unwrap();
+
+    let player = env.player.insecure_clone();
+    let ix = instructions::close_practice_session(&player.pubkey(), &session.address);
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::NotPracticeSession);
+}
+
+#[tokio::test]
+async fn paid_attempts_need_the_fee_ledgers() {
+    let mut env = TestEnv::new().await;
+    let mut session = env.setup_session().await;
+
+    // Building the instruction as if for practice leaves the ledgers out
+    session.is_practice = true;
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::MissingFeeLedgers);
+}
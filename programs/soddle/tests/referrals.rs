@@ -0,0 +1,132 @@
+mod common;
+
+use common::*;
+use soddle::constants::GAME_FEE;
+use soddle::errors::SoddleError;
+use soddle::states::fee_ledger::{FeeLedger, FeeParty};
+use soddle::states::game_session::GameSession;
+use soddle::states::referral::Referral;
+use soddle_client::{instructions, pda};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+const REFERRER_SHARE: u64 = GAME_FEE * 20 / 100;
+
+async fn funded_wallet(env: &mut TestEnv) -> Keypair {
+    let wallet = Keypair::new();
+    let ix = system_instruction::transfer(&env.authority(), &wallet.pubkey(), LAMPORTS_PER_SOL);
+    env.send(&[ix], &[]).await.unwrap();
+    wallet
+}
+
+#[tokio::test]
+async fn referrer_is_registered_once() {
+    let mut env = TestEnv::new().await;
+    let player = env.player.insecure_clone();
+    let referrer = Keypair::new().pubkey();
+
+    let ix = instructions::register_referrer(&player.pubkey(), &player.pubkey(), &[]);
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::SelfReferral);
+
+    let ix = instructions::register_referrer(&player.pubkey(), &referrer, &[]);
+    env.send(&[ix], &[&player]).await.unwrap();
+    let referral: Referral = env.account(&pda::referral(&player.pubkey()).0).await;
+    assert_eq!((referral.player, referral.referrer), (player.pubkey(), Some(referrer)));
+    let referrer_referral: Referral = env.account(&pda::referral(&referrer).0).await;
+    assert_eq!((referrer_referral.referrer, referrer_referral.referees), (None, 1));
+
+    let ix = instructions::register_referrer(&player.pubkey(), &Keypair::new().pubkey(), &[]);
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::ReferrerAlreadySet);
+}
+
+#[tokio::test]
+async fn referral_chains_cannot_loop() {
+    let mut env = TestEnv::new().await;
+    let player = env.player.insecure_clone();
+    let friend = funded_wallet(&mut env).await;
+    let other = funded_wallet(&mut env).await;
+
+    // player -> friend -> other
+    let ix = instructions::register_referrer(&player.pubkey(), &friend.pubkey(), &[]);
+    env.send(&[ix], &[&player]).await.unwrap();
+    let ix = instructions::register_referrer(&friend.pubkey(), &other.pubkey(), &[]);
+    env.send(&[ix], &[&friend]).await.unwrap();
+
+    // Naming the player would close the loop, through the friend as well as directly
+    let ix = instructions::register_referrer(&other.pubkey(), &player.pubkey(), &[friend.pubkey()]);
+    assert_soddle_error(env.send(&[ix], &[&other]).await, SoddleError::ReferralCycle);
+    let ix = instructions::register_referrer(&other.pubkey(), &friend.pubkey(), &[]);
+    assert_soddle_error(env.send(&[ix], &[&other]).await, SoddleError::ReferralCycle);
+
+    // The walk needs the whole chain above the referrer
+    let ix = instructions::register_referrer(&other.pubkey(), &player.pubkey(), &[]);
+    assert_soddle_error(env.send(&[ix], &[&other]).await, SoddleError::InvalidReferralChain);
+    let ix = instructions::register_referrer(&other.pubkey(), &player.pubkey(), &[player.pubkey()]);
+    assert_soddle_error(env.send(&[ix], &[&other]).await, SoddleError::InvalidReferralChain);
+
+    // Anyone outside the chain is fine
+    let ix = instructions::register_referrer(&other.pubkey(), &Keypair::new().pubkey(), &[]);
+    env.send(&[ix], &[&other]).await.unwrap();
+}
+
+#[tokio::test]
+async fn referrer_earns_and_claims_a_share_of_fees() {
+    let mut env = TestEnv::new().await;
+    let mut session = env.setup_session().await;
+    let ix = instructions::set_fee_split(&env.authority(), 8_000, 0, 0, 2_000);
+    env.send(&[ix], &[]).await.unwrap();
+
+    let referrer = funded_wallet(&mut env).await;
+    let player = env.player.insecure_clone();
+    let ix = instructions::register_referrer(&player.pubkey(), &referrer.pubkey(), &[]);
+    env.send(&[ix], &[&player]).await.unwrap();
+    session.referrer = Some(referrer.pubkey());
+
+    env.start_attempt(&session).await.unwrap();
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.referrer, Some(referrer.pubkey()));
+    assert_eq!(game_session.fee_split.referrer, REFERRER_SHARE);
+
+    let ledger_address = pda::referrer_ledger(&referrer.pubkey(), None).0;
+    let ledger: FeeLedger = env.account(&ledger_address).await;
+    assert_eq!((ledger.party, ledger.beneficiary), (FeeParty::Referrer, referrer.pubkey()));
+    assert_eq!((ledger.pending, ledger.balance), (REFERRER_SHARE, 0));
+
+    env.solve_keeping_deposit(&session).await;
+    env.end_session(&session).await.unwrap();
+    let ledger: FeeLedger = env.account(&ledger_address).await;
+    assert_eq!((ledger.pending, ledger.balance), (0, REFERRER_SHARE));
+
+    let before = env.lamports(&referrer.pubkey()).await;
+    let ix = instructions::claim_referral_rewards(&referrer.pubkey(), None);
+    env.send(&[ix], &[&referrer]).await.unwrap();
+    assert_eq!(env.lamports(&referrer.pubkey()).await, before + REFERRER_SHARE);
+
+    env.warp(1).await;
+    let ix = instructions::claim_referral_rewards(&referrer.pubkey(), None);
+    assert_soddle_error(env.send(&[ix], &[&referrer]).await, SoddleError::NoRewardsToClaim);
+}
+
+#[tokio::test]
+async fn sessions_charged_with_a_referrer_need_its_ledger_to_end() {
+    let mut env = TestEnv::new().await;
+    let mut session = env.setup_session().await;
+    let referrer = Keypair::new().pubkey();
+    let player = env.player.insecure_clone();
+    let ix = instructions::register_referrer(&player.pubkey(), &referrer, &[]);
+    env.send(&[ix], &[&player]).await.unwrap();
+    session.referrer = Some(referrer);
+
+    // The referrer's share can't be skipped by leaving their ledger out
+    session.referrer = None;
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::MissingReferrerLedger);
+    session.referrer = Some(referrer);
+    env.start_attempt(&session).await.unwrap();
+    env.solve_keeping_deposit(&session).await;
+
+    session.referrer = None;
+    assert_soddle_error(env.end_session(&session).await, SoddleError::MissingReferrerLedger);
+    session.referrer = Some(referrer);
+    env.end_session(&session).await.unwrap();
+}
@@ -0,0 +1,241 @@
+use proptest::prelude::*;
+use soddle::constants::*;
+use soddle::scoring::*;
+use soddle::states::score_model::{ScoreModel, WrongGuessPenalty};
+use soddle::states::scoring_rule::ScoringRule;
+
+const LINEAR: ScoringRule = ScoringRule::LinearDecay {
+    interval: TIME_PENALTY_INTERVAL,
+    penalty: TIME_PENALTY,
+};
+
+const FLAT: ScoreModel = ScoreModel {
+    wrong_guess_penalty: WrongGuessPenalty::Flat,
+    speed_bonus: 0,
+    speed_bonus_window: 0,
+};
+
+const CUMULATIVE: ScoreModel = ScoreModel {
+    wrong_guess_penalty: WrongGuessPenalty::Cumulative,
+    ..FLAT
+};
+
+#[test]
+fn instant_correct_guess_scores_full_marks() {
+    let breakdown = score_guess(&LINEAR, &FLAT, 1_000, 1_000, 0, true);
+    assert_eq!(
+        breakdown,
+        ScoreBreakdown {
+            base: INITIAL_SCORE,
+            time_deduction: 0,
+            wrong_guess_deduction: 0,
+            bonus: 0,
+            final_score: INITIAL_SCORE,
+        }
+    );
+}
+
+#[test]
+fn time_is_charged_per_full_interval() {
+    let start = 1_000;
+    assert_eq!(time_deduction(start, start + TIME_PENALTY_INTERVAL - 1), 0);
+    assert_eq!(time_deduction(start, start + TIME_PENALTY_INTERVAL), TIME_PENALTY);
+    assert_eq!(time_deduction(start, start + 3 * TIME_PENALTY_INTERVAL + 1), 3 * TIME_PENALTY);
+}
+
+#[test]
+fn flat_model_only_charges_the_latest_guess() {
+    let breakdown = score_guess(&LINEAR, &FLAT, 0, 2 * TIME_PENALTY_INTERVAL, 4, false);
+    assert_eq!(breakdown.wrong_guess_deduction, WRONG_GUESS_PENALTY);
+    assert_eq!(breakdown.final_score, INITIAL_SCORE - 2 * TIME_PENALTY - WRONG_GUESS_PENALTY);
+    assert_eq!(score_guess(&LINEAR, &FLAT, 0, 0, 4, true).wrong_guess_deduction, 0);
+}
+
+#[test]
+fn cumulative_model_remembers_earlier_wrong_guesses() {
+    assert_eq!(score_guess(&LINEAR, &CUMULATIVE, 0, 0, 0, false).wrong_guess_deduction, WRONG_GUESS_PENALTY);
+    assert_eq!(score_guess(&LINEAR, &CUMULATIVE, 0, 0, 9, false).wrong_guess_deduction, 10 * WRONG_GUESS_PENALTY);
+    // Solving keeps the earlier penalties
+    assert_eq!(score_guess(&LINEAR, &CUMULATIVE, 0, 0, 3, true).final_score, INITIAL_SCORE - 3 * WRONG_GUESS_PENALTY);
+}
+
+#[test]
+fn escalating_model_grows_per_wrong_guess() {
+    let penalty = WrongGuessPenalty::Escalating { step: 10 };
+    assert_eq!(wrong_guess_deduction(penalty, 0, false), 0);
+    assert_eq!(wrong_guess_deduction(penalty, 1, true), WRONG_GUESS_PENALTY);
+    // P + (P + 10) + (P + 20)
+    assert_eq!(wrong_guess_deduction(penalty, 3, true), 3 * WRONG_GUESS_PENALTY + 30);
+    assert_eq!(wrong_guess_deduction(penalty, u32::MAX, true), u32::MAX);
+}
+
+#[test]
+fn speed_bonus_shrinks_over_its_window() {
+    let model = ScoreModel {
+        speed_bonus: 200,
+        speed_bonus_window: 100,
+        ..FLAT
+    };
+    assert_eq!(speed_bonus(&model, 0, 0), 200);
+    assert_eq!(speed_bonus(&model, 0, 25), 150);
+    assert_eq!(speed_bonus(&model, 0, 100), 0);
+    assert_eq!(score_guess(&LINEAR, &model, 0, 0, 0, true).final_score, INITIAL_SCORE + 200);
+    // Only correct answers earn it
+    assert_eq!(score_guess(&LINEAR, &model, 0, 0, 0, false).bonus, 0);
+}
+
+#[test]
+fn default_rule_is_the_original_linear_curve() {
+    assert_eq!(ScoringRule::default(), LINEAR);
+}
+
+#[test]
+fn exponential_decay_halves_per_half_life() {
+    let rule = ScoringRule::ExponentialDecay { half_life: 60 };
+    assert_eq!(score_guess(&rule, &FLAT, 0, 0, 0, true).final_score, INITIAL_SCORE);
+    assert_eq!(score_guess(&rule, &FLAT, 0, 30, 0, true).final_score, 750);
+    assert_eq!(score_guess(&rule, &FLAT, 0, 60, 0, true).final_score, 500);
+    assert_eq!(score_guess(&rule, &FLAT, 0, 120, 0, true).final_score, 250);
+    assert_eq!(exponential_remaining(INITIAL_SCORE, 1, i64::MAX), 0);
+}
+
+#[test]
+fn guess_count_only_ignores_the_clock() {
+    let rule = ScoringRule::GuessCountOnly;
+    assert_eq!(score_guess(&rule, &CUMULATIVE, 0, 10_000, 2, true).final_score, INITIAL_SCORE - 2 * WRONG_GUESS_PENALTY);
+    assert_eq!(score_guess(&rule, &CUMULATIVE, 0, 10_000, 2, true).time_deduction, 0);
+}
+
+#[test]
+fn fixed_points_only_pay_for_a_solve() {
+    let rule = ScoringRule::FixedPoints { points: 300 };
+    assert_eq!(score_guess(&rule, &CUMULATIVE, 0, 10_000, 7, true).final_score, 300);
+    assert_eq!(score_guess(&rule, &CUMULATIVE, 0, 0, 0, false).final_score, 0);
+}
+
+#[test]
+fn days_start_at_midnight_utc() {
+    assert_eq!(day_index(0), 0);
+    assert_eq!(day_index(SECONDS_PER_DAY - 1), 0);
+    assert_eq!(day_index(SECONDS_PER_DAY), 1);
+    assert_eq!(day_index(-1), -1);
+}
+
+#[test]
+fn streak_bonus_starts_on_the_second_day_and_is_capped() {
+    assert_eq!(streak_bonus_bps(0, 500, 2_000), 0);
+    assert_eq!(streak_bonus_bps(1, 500, 2_000), 0);
+    assert_eq!(streak_bonus_bps(3, 500, 2_000), 1_000);
+    assert_eq!(streak_bonus_bps(30, 500, 2_000), 2_000);
+    assert_eq!(apply_bonus(990, 1_000), 1_089);
+    assert_eq!(apply_bonus(u32::MAX, 10_000), u32::MAX);
+}
+
+#[test]
+fn limits_are_inclusive() {
+    assert!(!is_attempt_timed_out(0, ATTEMPT_TIME_LIMIT - 1));
+    assert!(is_attempt_timed_out(0, ATTEMPT_TIME_LIMIT));
+    assert!(!is_session_expired(0, SESSION_DURATION - 1));
+    assert!(is_session_expired(0, SESSION_DURATION));
+}
+
+#[test]
+fn clock_behind_the_start_counts_as_no_time() {
+    assert_eq!(elapsed(100, 40), 0);
+    assert_eq!(score_guess(&LINEAR, &FLAT, 100, 40, 0, true).final_score, INITIAL_SCORE);
+}
+
+fn any_rule() -> impl Strategy<Value = ScoringRule> {
+    prop_oneof![
+        (1i64..1_000, 0u32..100).prop_map(|(interval, penalty)| ScoringRule::LinearDecay { interval, penalty }),
+        (1i64..1_000).prop_map(|half_life| ScoringRule::ExponentialDecay { half_life }),
+        (0u32..5_000).prop_map(|points| ScoringRule::FixedPoints { points }),
+        Just(ScoringRule::GuessCountOnly),
+    ]
+}
+
+fn any_model() -> impl Strategy<Value = ScoreModel> {
+    let penalty = prop_oneof![
+        Just(WrongGuessPenalty::Flat),
+        Just(WrongGuessPenalty::Cumulative),
+        (0u32..500).prop_map(|step| WrongGuessPenalty::Escalating { step }),
+    ];
+    (penalty, 0u32..1_000, -10i64..1_000).prop_map(|(wrong_guess_penalty, speed_bonus, speed_bonus_window)| {
+        ScoreModel {
+            wrong_guess_penalty,
+            speed_bonus,
+            speed_bonus_window,
+        }
+    })
+}
+
+// The models initialize_competition accepts
+fn valid_model() -> impl Strategy<Value = ScoreModel> {
+    let bonus = prop_oneof![Just((0, 0)), (1..=INITIAL_SCORE, 1..=ATTEMPT_TIME_LIMIT)];
+    (any_model(), bonus).prop_map(|(model, (speed_bonus, speed_bonus_window))| ScoreModel {
+        speed_bonus,
+        speed_bonus_window,
+        ..model
+    })
+}
+
+proptest! {
+    #[test]
+    fn final_score_matches_breakdown(
+        rule in any_rule(),
+        model in any_model(),
+        start in any::<i64>(),
+        now in any::<i64>(),
+        wrong in 0u32..=MAX_GUESSES,
+        correct in any::<bool>(),
+    ) {
+        let b = score_guess(&rule, &model, start, now, wrong, correct);
+        prop_assert_eq!(
+            b.final_score,
+            b.base.saturating_sub(b.time_deduction).saturating_sub(b.wrong_guess_deduction).saturating_add(b.bonus)
+        );
+    }
+
+    #[test]
+    fn score_never_increases_with_time(
+        rule in any_rule(),
+        model in any_model(),
+        start in -1_000_000i64..1_000_000,
+        a in 0i64..100_000,
+        b in 0i64..100_000,
+        wrong in 0u32..=MAX_GUESSES,
+        correct in any::<bool>(),
+    ) {
+        let (early, late) = (a.min(b), a.max(b));
+        prop_assert!(
+            score_guess(&rule, &model, start, start + late, wrong, correct).final_score
+                <= score_guess(&rule, &model, start, start + early, wrong, correct).final_score
+        );
+    }
+
+    #[test]
+    fn more_wrong_guesses_never_score_higher(
+        rule in any_rule(),
+        model in any_model(),
+        offset in 0i64..100_000,
+        wrong in 0u32..MAX_GUESSES,
+        correct in any::<bool>(),
+    ) {
+        prop_assert!(
+            score_guess(&rule, &model, 0, offset, wrong + 1, correct).final_score
+                <= score_guess(&rule, &model, 0, offset, wrong, correct).final_score
+        );
+    }
+
+    #[test]
+    fn valid_competitions_score_at_most_twice_the_initial_score(
+        rule in any_rule().prop_filter("valid", ScoringRule::is_valid),
+        model in valid_model(),
+        start in any::<i64>(),
+        now in any::<i64>(),
+        wrong in 0u32..=MAX_GUESSES,
+        correct in any::<bool>(),
+    ) {
+        prop_assert!(score_guess(&rule, &model, start, now, wrong, correct).final_score <= 2 * INITIAL_SCORE);
+    }
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::*;
+use soddle::constants::{GAME_FEE, MAX_DAILY_ATTEMPTS, SECONDS_PER_DAY};
+use soddle::errors::SoddleError;
+use soddle::states::{game_metrics::GameMetrics, game_session::GameSession, season_pass::SeasonPass};
+use soddle_client::{instructions, pda};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+
+const PRICE: u64 = LAMPORTS_PER_SOL / 10;
+const DURATION: i64 = 30 * SECONDS_PER_DAY;
+const BONUS_ATTEMPTS: u8 = 2;
+
+/// A session whose player holds a pass bought for `duration` seconds.
+async fn pass_holder_session(env: &mut TestEnv, duration: i64) -> Session {
+    let mut session = env.setup_session().await;
+    buy_pass(env, &mut session, duration).await;
+    session
+}
+
+async fn buy_pass(env: &mut TestEnv, session: &mut Session, duration: i64) {
+    let ix = instructions::set_season_pass_config(&env.authority(), PRICE, duration, BONUS_ATTEMPTS);
+    env.send(&[ix], &[]).await.unwrap();
+
+    let player = env.player.insecure_clone();
+    env.send(&[instructions::buy_season_pass(&player.pubkey())], &[&player]).await.unwrap();
+    session.season_pass = true;
+}
+
+async fn play_attempts(env: &mut TestEnv, session: &Session, attempts: u8) {
+    for _ in 0..attempts {
+        env.start_attempt(session).await.unwrap();
+        env.guess(session, ANSWER).await.unwrap();
+        // A fresh slot, so the next identical start isn't deduplicated
+        env.warp(0).await;
+    }
+}
+
+#[tokio::test]
+async fn pass_revenue_is_kept_apart_from_fees() {
+    let mut env = TestEnv::new().await;
+    let fee_vault = pda::fee_vault().0;
+    let mut session = env.setup_session().await;
+    let vault_before = env.lamports(&fee_vault).await;
+    buy_pass(&mut env, &mut session, DURATION).await;
+    let now = env.now().await;
+
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + PRICE);
+    let metrics: GameMetrics = env.account(&pda::game_metrics().0).await;
+    assert_eq!((metrics.total_season_pass_revenue, metrics.total_fees_collected), (PRICE, 0));
+
+    let pass: SeasonPass = env.account(&pda::season_pass(&env.player.pubkey()).0).await;
+    assert_eq!(pass.player, env.player.pubkey());
+    assert_eq!(pass.expires_at, now + DURATION);
+    assert_eq!((pass.bonus_attempts, pass.total_paid), (BONUS_ATTEMPTS, PRICE));
+
+    // Attempts under the pass are not charged
+    env.start_attempt(&session).await.unwrap();
+    assert_eq!(env.lamports(&fee_vault).await, vault_before + PRICE);
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.deposit, 0);
+}
+
+#[tokio::test]
+async fn buying_again_extends_the_pass() {
+    let mut env = TestEnv::new().await;
+    pass_holder_session(&mut env, DURATION).await;
+    let player = env.player.insecure_clone();
+    let first: SeasonPass = env.account(&pda::season_pass(&player.pubkey()).0).await;
+
+    env.warp(1).await;
+    env.send(&[instructions::buy_season_pass(&player.pubkey())], &[&player]).await.unwrap();
+    let pass: SeasonPass = env.account(&pda::season_pass(&player.pubkey()).0).await;
+    assert_eq!(pass.expires_at, first.expires_at + DURATION);
+    assert_eq!(pass.total_paid, 2 * PRICE);
+}
+
+#[tokio::test]
+async fn pass_holders_get_bonus_attempts() {
+    let mut env = TestEnv::new().await;
+    let session = pass_holder_session(&mut env, DURATION).await;
+
+    play_attempts(&mut env, &session, MAX_DAILY_ATTEMPTS + BONUS_ATTEMPTS).await;
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::MaxAttemptsReached);
+}
+
+#[tokio::test]
+async fn lapsed_pass_charges_the_fee_again() {
+    let mut env = TestEnv::new().await;
+    let first = pass_holder_session(&mut env, 10).await;
+    env.warp(10).await;
+    // Attempts start no later than their session, so the lapsed pass is used on a later one
+    let session = Session { season_pass: true, ..env.open_session(first.competition).await };
+
+    play_attempts(&mut env, &session, MAX_DAILY_ATTEMPTS).await;
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.deposit, MAX_DAILY_ATTEMPTS as u64 * GAME_FEE);
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::MaxAttemptsReached);
+}
+
+#[tokio::test]
+async fn season_pass_config_is_validated() {
+    let mut env = TestEnv::new().await;
+    env.setup_session().await;
+    let authority = env.authority();
+    let player = env.player.insecure_clone();
+
+    // Not on sale until priced
+    let ix = instructions::buy_season_pass(&player.pubkey());
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::SeasonPassUnavailable);
+
+    let ix = instructions::set_season_pass_config(&authority, PRICE, 0, 0);
+    assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidSeasonPassConfig);
+    let ix = instructions::set_season_pass_config(&authority, PRICE, DURATION, 4);
+    assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidSeasonPassConfig);
+}
@@ -0,0 +1,275 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{transfer_fee, transfer_hook, ExtensionType};
+use anchor_spl::token_2022::spl_token_2022::{self, state::Mint};
+use common::*;
+use soddle::constants::SECONDS_PER_DAY;
+use soddle::errors::SoddleError;
+use soddle::states::game_session::GameSession;
+use soddle::states::player_profile::PlayerProfile;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle::states::token_metrics::TokenMetrics;
+use soddle_client::instructions::{self, AttemptOptions, TokenFee};
+use soddle_client::pda;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+// One "USDC": six decimals
+const FEE: u64 = 1_000_000;
+
+struct TokenSetup {
+    mint: Pubkey,
+    player_account: Pubkey,
+}
+
+/// Metrics, config, KOL, a mint with its vault, and a competition charging `FEE` in it.
+async fn setup_token_session(env: &mut TestEnv) -> (Session, TokenSetup) {
+    env.initialize_metrics().await;
+    env.initialize_kol().await;
+
+    let mint = env.create_mint(6, &token_2022::ID).await;
+    let ix = instructions::initialize_token_vault(&env.authority(), &mint, &token_2022::ID);
+    env.send(&[ix], &[]).await.unwrap();
+
+    let player = env.player.pubkey();
+    let player_account = env.create_token_account(&mint, &player, &token_2022::ID, 5 * FEE).await;
+    let competition = env
+        .initialize_competition_with_fee(ScoringRule::default(), ScoreModel::default(), Some(mint), FEE)
+        .await;
+
+    let mut session = env.open_session(competition).await;
+    session.fee = Some(TokenFee {
+        mint,
+        token_program: token_2022::ID,
+        token_account: player_account,
+    });
+    (session, TokenSetup { mint, player_account })
+}
+
+#[tokio::test]
+async fn token_competitions_charge_the_mint_instead_of_lamports() {
+    let mut env = TestEnv::new().await;
+    let (session, token) = setup_token_session(&mut env).await;
+    let fee_vault = pda::fee_vault().0;
+    let vault_lamports = env.lamports(&fee_vault).await;
+
+    // The currency is settled before the first attempt
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.fee_mint, Some(token.mint));
+
+    env.start_attempt(&session).await.unwrap();
+
+    assert_eq!(env.token_balance(&token.player_account).await, 4 * FEE);
+    assert_eq!(env.token_balance(&pda::token_vault(&token.mint).0).await, FEE);
+    assert_eq!(env.lamports(&fee_vault).await, vault_lamports);
+
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.deposit, FEE);
+    assert_eq!(game_session.fee_mint, Some(token.mint));
+
+    let metrics: TokenMetrics = env.account(&pda::token_metrics(&token.mint).0).await;
+    assert_eq!(metrics.mint, token.mint);
+    assert_eq!(metrics.total_fees_collected, FEE);
+}
+
+#[tokio::test]
+async fn quick_solve_refunds_the_token_deposit() {
+    let mut env = TestEnv::new().await;
+    let (session, token) = setup_token_session(&mut env).await;
+    env.start_attempt(&session).await.unwrap();
+    env.guess(&session, ANSWER).await.unwrap();
+    env.end_session(&session).await.unwrap();
+
+    assert_eq!(env.token_balance(&token.player_account).await, 5 * FEE);
+    assert_eq!(env.token_balance(&pda::token_vault(&token.mint).0).await, 0);
+    let metrics: TokenMetrics = env.account(&pda::token_metrics(&token.mint).0).await;
+    assert_eq!(metrics.total_refunded, FEE);
+
+    // The profile's totals are in lamports
+    let profile: PlayerProfile = env.account(&pda::player_profile(&env.player.pubkey()).0).await;
+    assert_eq!((profile.games_played, profile.total_fees_paid, profile.total_refunds), (1, 0, 0));
+}
+
+#[tokio::test]
+async fn season_passes_do_not_cover_token_fees() {
+    let mut env = TestEnv::new().await;
+    let (mut session, token) = setup_token_session(&mut env).await;
+    let ix = instructions::set_season_pass_config(&env.authority(), LAMPORTS_PER_SOL / 10, SECONDS_PER_DAY, 0);
+    env.send(&[ix], &[]).await.unwrap();
+    let player = env.player.insecure_clone();
+    env.send(&[instructions::buy_season_pass(&player.pubkey())], &[&player]).await.unwrap();
+    session.season_pass = true;
+
+    env.start_attempt(&session).await.unwrap();
+    assert_eq!(env.token_balance(&token.player_account).await, 4 * FEE);
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.deposit, FEE);
+}
+
+#[tokio::test]
+async fn token_fee_accounts_must_match_the_competition() {
+    let mut env = TestEnv::new().await;
+    let (session, token) = setup_token_session(&mut env).await;
+    let player = env.player.insecure_clone();
+
+    // The ledgers of the competition's mint, but none of the token accounts
+    let fee = session.fee.unwrap();
+    let token_accounts = [
+        token.mint,
+        pda::token_vault(&token.mint).0,
+        pda::token_metrics(&token.mint).0,
+        token.player_account,
+        fee.token_program,
+    ];
+    let mut ix = instructions::start_game_session(
+        &player.pubkey(),
+        &pda::kol(KOL_ID).0,
+        &session.competition,
+        COMPETITION_ID,
+        session.start_time,
+        AttemptOptions { fee: Some(fee), ..AttemptOptions::default() },
+    );
+    for meta in ix.accounts.iter_mut().filter(|meta| token_accounts.contains(&meta.pubkey)) {
+        meta.pubkey = soddle::ID;
+        meta.is_writable = false;
+    }
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::MissingTokenAccounts);
+
+    // A mint with a vault of its own, but not the one the competition charges in
+    let other_mint = env.create_mint(6, &token_2022::ID).await;
+    let ix = instructions::initialize_token_vault(&env.authority(), &other_mint, &token_2022::ID);
+    env.send(&[ix], &[]).await.unwrap();
+    let other_account = env.create_token_account(&other_mint, &player.pubkey(), &token_2022::ID, FEE).await;
+    let swaps = [
+        (token.mint, other_mint),
+        (pda::token_vault(&token.mint).0, pda::token_vault(&other_mint).0),
+        (pda::token_metrics(&token.mint).0, pda::token_metrics(&other_mint).0),
+        (token.player_account, other_account),
+    ];
+    // Ledgers stay keyed by the competition's mint, as the program derives them
+    let mut ix = instructions::start_game_session(
+        &player.pubkey(),
+        &pda::kol(KOL_ID).0,
+        &session.competition,
+        COMPETITION_ID,
+        session.start_time,
+        AttemptOptions { fee: Some(fee), ..AttemptOptions::default() },
+    );
+    for meta in ix.accounts.iter_mut() {
+        if let Some((_, other)) = swaps.iter().find(|(ours, _)| *ours == meta.pubkey) {
+            meta.pubkey = *other;
+        }
+    }
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::InvalidFeeMint);
+}
+
+#[tokio::test]
+async fn authority_withdraws_token_fees() {
+    let mut env = TestEnv::new().await;
+    let (session, token) = setup_token_session(&mut env).await;
+    env.start_attempt(&session).await.unwrap();
+    env.solve_keeping_deposit(&session).await;
+    env.end_session(&session).await.unwrap();
+
+    let authority = env.authority();
+    let treasury = env.create_token_account(&token.mint, &authority, &token_2022::ID, 0).await;
+    let withdrawal = TokenFee {
+        mint: token.mint,
+        token_program: token_2022::ID,
+        token_account: treasury,
+    };
+
+    // The receiving token account has to belong to the authority
+    let stranger = Keypair::new();
+    let stranger_account = env.create_token_account(&token.mint, &stranger.pubkey(), &token_2022::ID, 0).await;
+    let ix = instructions::withdraw_fees(
+        &authority,
+        &authority,
+        FEE,
+        Some(TokenFee {
+            token_account: stranger_account,
+            ..withdrawal
+        }),
+    );
+    assert!(env.send(&[ix], &[]).await.is_err());
+
+    let ix = instructions::withdraw_fees(&authority, &authority, FEE + 1, Some(withdrawal));
+    assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InsufficientFunds);
+
+    let ix = instructions::withdraw_fees(&authority, &authority, FEE, Some(withdrawal));
+    env.send(&[ix], &[]).await.unwrap();
+    assert_eq!(env.token_balance(&treasury).await, FEE);
+    let metrics: TokenMetrics = env.account(&pda::token_metrics(&token.mint).0).await;
+    assert_eq!(metrics.total_withdrawn, FEE);
+}
+
+#[tokio::test]
+async fn token_fees_need_a_mint_and_an_amount() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    env.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+    let game_state = pda::game_state(&authority).0;
+    let now = env.now().await;
+    let mint = env.create_mint(6, &token_2022::ID).await;
+
+    for (id, fee_mint, fee_amount) in [(2, Some(mint), 0), (3, None, FEE)] {
+        let ix = instructions::initialize_competition(
+            &authority,
+            &game_state,
+            id,
+            now + 10,
+            now + 50,
+            ScoringRule::default(),
+            ScoreModel::default(),
+            fee_mint,
+            fee_amount,
+            None,
+        );
+        assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidFeeAmount);
+    }
+}
+
+
+#[tokio::test]
+async fn vaults_reject_mints_with_transfer_fees_or_hooks() {
+    let mut env = TestEnv::new().await;
+    env.initialize_metrics().await;
+    let authority = env.authority();
+    let rent = env.ctx.banks_client.get_rent().await.unwrap();
+
+    for extension in [ExtensionType::TransferFeeConfig, ExtensionType::TransferHook] {
+        let mint = Keypair::new();
+        let space = ExtensionType::try_calculate_account_len::<Mint>(&[extension]).unwrap();
+        let init_extension = match extension {
+            ExtensionType::TransferFeeConfig => transfer_fee::instruction::initialize_transfer_fee_config(
+                &token_2022::ID,
+                &mint.pubkey(),
+                Some(&authority),
+                Some(&authority),
+                100,
+                FEE,
+            ),
+            _ => transfer_hook::instruction::initialize(&token_2022::ID, &mint.pubkey(), Some(authority), Some(soddle::ID)),
+        };
+        let ixs = [
+            system_instruction::create_account(
+                &authority,
+                &mint.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &token_2022::ID,
+            ),
+            init_extension.unwrap(),
+            spl_token_2022::instruction::initialize_mint2(&token_2022::ID, &mint.pubkey(), &authority, None, 6).unwrap(),
+        ];
+        env.send(&ixs, &[&mint]).await.unwrap();
+
+        // The vault would receive less than the fee, or nothing at all
+        let ix = instructions::initialize_token_vault(&authority, &mint.pubkey(), &token_2022::ID);
+        assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::UnsupportedMintExtension);
+    }
+}
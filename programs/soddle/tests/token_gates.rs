@@ -0,0 +1,108 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token_2022;
+use common::*;
+use soddle::errors::SoddleError;
+use soddle::states::score_model::ScoreModel;
+use soddle::states::scoring_rule::ScoringRule;
+use soddle::states::token_gate::TokenGate;
+use soddle_client::instructions::{self, TokenHolding};
+use soddle_client::pda;
+use solana_sdk::signature::{Keypair, Signer};
+
+const MIN_AMOUNT: u64 = 100;
+
+async fn gated_session(env: &mut TestEnv, gate: TokenGate) -> Session {
+    env.initialize_metrics().await;
+    env.initialize_kol().await;
+    let competition = env.initialize_gated_competition(gate).await;
+    env.open_session(competition).await
+}
+
+async fn holding(env: &mut TestEnv, mint: &Pubkey, owner: &Pubkey, amount: u64) -> TokenHolding {
+    TokenHolding {
+        mint: *mint,
+        token_account: env.create_token_account(mint, owner, &token_2022::ID, amount).await,
+    }
+}
+
+#[tokio::test]
+async fn holders_of_enough_tokens_can_enter() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_mint(0, &token_2022::ID).await;
+    let mut session = gated_session(&mut env, TokenGate::Balance { mint, min_amount: MIN_AMOUNT }).await;
+    let player = env.player.pubkey();
+
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::TokenGateNotMet);
+
+    session.holding = Some(holding(&mut env, &mint, &player, MIN_AMOUNT - 1).await);
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::TokenGateNotMet);
+
+    // Someone else's tokens don't count
+    let stranger = Keypair::new().pubkey();
+    session.holding = Some(holding(&mut env, &mint, &stranger, MIN_AMOUNT).await);
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::TokenGateNotMet);
+
+    // Nor do the player's tokens of another mint
+    let other_mint = env.create_mint(0, &token_2022::ID).await;
+    session.holding = Some(holding(&mut env, &other_mint, &player, MIN_AMOUNT).await);
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::TokenGateNotMet);
+
+    session.holding = Some(holding(&mut env, &mint, &player, MIN_AMOUNT).await);
+    env.start_attempt(&session).await.unwrap();
+}
+
+#[tokio::test]
+async fn an_ungated_lookalike_does_not_open_the_gate() {
+    let mut env = TestEnv::new().await;
+    let mint = env.create_mint(0, &token_2022::ID).await;
+    let mut session = gated_session(&mut env, TokenGate::Balance { mint, min_amount: MIN_AMOUNT }).await;
+    let competition = session.competition;
+
+    // Same id, no gate, and no tokens shown for it
+    session.competition = env.lookalike_competition().await;
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::InvalidCompetition);
+
+    session.competition = competition;
+    assert_soddle_error(env.start_attempt(&session).await, SoddleError::TokenGateNotMet);
+}
+
+#[tokio::test]
+async fn collection_gates_need_a_member_of_the_collection() {
+    let mut env = TestEnv::new().await;
+    let collection = env.create_mint(0, &token_2022::ID).await;
+    let mut session = gated_session(&mut env, TokenGate::Collection { collection }).await;
+    let player = env.player.pubkey();
+
+    // A plain mint belongs to no group, not even when it is the collection itself
+    for mint in [env.create_mint(0, &token_2022::ID).await, collection] {
+        session.holding = Some(holding(&mut env, &mint, &player, 1).await);
+        assert_soddle_error(env.start_attempt(&session).await, SoddleError::TokenGateNotMet);
+        env.warp(1).await;
+    }
+}
+
+#[tokio::test]
+async fn balance_gates_need_an_amount() {
+    let mut env = TestEnv::new().await;
+    let authority = env.authority();
+    env.send(&[instructions::initialize_game_state(&authority)], &[]).await.unwrap();
+    let game_state = pda::game_state(&authority).0;
+    let now = env.now().await;
+    let mint = env.create_mint(0, &token_2022::ID).await;
+
+    let ix = instructions::initialize_competition(
+        &authority,
+        &game_state,
+        COMPETITION_ID,
+        now + 10,
+        now + 50,
+        ScoringRule::default(),
+        ScoreModel::default(),
+        None,
+        0,
+        Some(TokenGate::Balance { mint, min_amount: 0 }),
+    );
+    assert_soddle_error(env.send(&[ix], &[]).await, SoddleError::InvalidTokenGate);
+}
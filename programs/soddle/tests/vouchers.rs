@@ -0,0 +1,138 @@
+mod common;
+
+use common::*;
+use soddle::constants::{GAME_FEE, VOUCHERS_PER_BITMAP};
+use soddle::errors::SoddleError;
+use soddle::states::game_session::GameSession;
+use soddle::states::voucher::{SignedVoucher, Voucher, VoucherBitmap};
+use soddle_client::instructions::{self, AttemptOptions};
+use soddle_client::pda;
+use solana_sdk::signature::{Keypair, Signer};
+
+async fn voucher(env: &mut TestEnv, session: &Session, discount_bps: u16, nonce: u64) -> Voucher {
+    Voucher {
+        player: Some(env.player.pubkey()),
+        competition: session.competition,
+        discount_bps,
+        expires_at: env.now().await + 60,
+        nonce,
+    }
+}
+
+#[tokio::test]
+async fn full_discount_makes_the_attempt_free() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    let fee_vault = pda::fee_vault().0;
+    let vault_before = env.lamports(&fee_vault).await;
+
+    let free = voucher(&mut env, &session, 10_000, VOUCHERS_PER_BITMAP + 3).await;
+    env.start_attempt_with_voucher(&session, free).await.unwrap();
+
+    assert_eq!(env.lamports(&fee_vault).await, vault_before);
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!((game_session.current_attempt, game_session.deposit), (1, 0));
+
+    let bitmap: VoucherBitmap = env.account(&pda::voucher_bitmap(1).0).await;
+    assert_eq!(bitmap.page, 1);
+    assert_eq!(bitmap.bits[0], 1 << 3);
+}
+
+#[tokio::test]
+async fn vouchers_are_redeemed_once() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+
+    let half = voucher(&mut env, &session, 5_000, 42).await;
+    env.start_attempt_with_voucher(&session, half).await.unwrap();
+    let game_session: GameSession = env.account(&session.address).await;
+    assert_eq!(game_session.deposit, GAME_FEE / 2);
+
+    env.warp(0).await;
+    assert_soddle_error(
+        env.start_attempt_with_voucher(&session, half).await,
+        SoddleError::VoucherAlreadyUsed,
+    );
+
+    // Another nonce on the same page is still good
+    let other = Voucher { nonce: 43, ..half };
+    env.start_attempt_with_voucher(&session, other).await.unwrap();
+}
+
+#[tokio::test]
+async fn expired_or_foreign_vouchers_are_refused() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    let valid = voucher(&mut env, &session, 10_000, 1).await;
+
+    let expired = Voucher { expires_at: env.now().await, ..valid };
+    assert_soddle_error(
+        env.start_attempt_with_voucher(&session, expired).await,
+        SoddleError::VoucherExpired,
+    );
+
+    let someone_elses = Voucher { player: Some(Keypair::new().pubkey()), ..valid };
+    assert_soddle_error(
+        env.start_attempt_with_voucher(&session, someone_elses).await,
+        SoddleError::InvalidVoucher,
+    );
+
+    let other_competition = Voucher { competition: Keypair::new().pubkey(), ..valid };
+    assert_soddle_error(
+        env.start_attempt_with_voucher(&session, other_competition).await,
+        SoddleError::InvalidVoucher,
+    );
+
+    let overdrawn = Voucher { discount_bps: 10_001, ..valid };
+    assert_soddle_error(
+        env.start_attempt_with_voucher(&session, overdrawn).await,
+        SoddleError::InvalidVoucher,
+    );
+
+    // Open vouchers can be redeemed by whoever holds them
+    let open = Voucher { player: None, ..valid };
+    env.start_attempt_with_voucher(&session, open).await.unwrap();
+}
+
+#[tokio::test]
+async fn vouchers_need_the_authority_signature() {
+    let mut env = TestEnv::new().await;
+    let session = env.setup_session().await;
+    let free = voucher(&mut env, &session, 10_000, 7).await;
+    let player = env.player.insecure_clone();
+
+    // Signed by the player instead of the metrics authority
+    let signed = SignedVoucher {
+        voucher: free,
+        signature: player.sign_message(&free.message()).into(),
+    };
+    let ixs = [
+        instructions::ed25519_verify(&player.pubkey(), &signed.signature, &free.message()),
+        instructions::start_game_session(
+            &player.pubkey(),
+            &pda::kol(KOL_ID).0,
+            &session.competition,
+            COMPETITION_ID,
+            session.start_time,
+            AttemptOptions {
+                voucher: Some(signed),
+                ..AttemptOptions::default()
+            },
+        ),
+    ];
+    assert_soddle_error(env.send(&ixs, &[&player]).await, SoddleError::InvalidVoucher);
+
+    // Without the Ed25519 verification at all
+    let ix = instructions::start_game_session(
+        &player.pubkey(),
+        &pda::kol(KOL_ID).0,
+        &session.competition,
+        COMPETITION_ID,
+        session.start_time,
+        AttemptOptions {
+            voucher: Some(SignedVoucher { voucher: free, signature: [0; 64] }),
+            ..AttemptOptions::default()
+        },
+    );
+    assert_soddle_error(env.send(&[ix], &[&player]).await, SoddleError::InvalidVoucher);
+}